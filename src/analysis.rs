@@ -16,7 +16,7 @@ pub fn clustering_coefficient(graph: &HashMap<usize, HashSet<usize>>) -> HashMap
         // Check pairs of neighbors for possible triangles
         for &neighbor in neighbors {
             for &other_neighbor in neighbors {
-                if neighbor != other_neighbor && graph.get(&neighbor).map_or(false, |n| n.contains(&other_neighbor)) {
+                if neighbor != other_neighbor && graph.get(&neighbor).is_some_and(|n| n.contains(&other_neighbor)) {
                     triangles += 1;
                 }
                 possible_triangles += 1;
@@ -34,6 +34,27 @@ pub fn clustering_coefficient(graph: &HashMap<usize, HashSet<usize>>) -> HashMap
     coefficients
 }
 
+// AVERAGE CLUSTERING. Mean local clustering coefficient over all nodes: the share of the d(d-1)/2 pairs of a node's d neighbors that are linked
+// (nodes with fewer than two neighbors count as 0). clustering_coefficient above also counts each neighbor paired with itself as a possible triangle.
+pub fn average_clustering(graph: &HashMap<usize, HashSet<usize>>) -> f64 {
+    if graph.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = graph
+        .values()
+        .map(|neighbors| {
+            let degree = neighbors.len();
+            if degree < 2 {
+                return 0.0;
+            }
+            // Each link between two neighbors is seen from both ends
+            let links: usize = neighbors.iter().map(|n| graph.get(n).map_or(0, |others| others.intersection(neighbors).count())).sum();
+            links as f64 / (degree * (degree - 1)) as f64
+        })
+        .sum();
+    total / graph.len() as f64
+}
+
 #[allow(dead_code)] 
 pub fn graph_diameter(graph: &HashMap<usize, HashSet<usize>>) -> usize {
     let mut max_distance = 0;
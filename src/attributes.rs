@@ -0,0 +1,63 @@
+// Attribute homophily: do follow relationships cluster by shared hashtags and mentions? Uses the sparse binary node features loaded by snap::read_features.
+
+use std::collections::{HashMap, HashSet};
+
+// FEATURE HOMOPHILY. For every feature, over the edges whose endpoints both have feature data: (feature index, number of nodes with the feature,
+// homophily = fraction of those nodes' edges that lead to another node with the feature, assortativity coefficient). Assortativity is Newman's coefficient
+// for the two classes "has the feature" / "does not": 1 when edges only join nodes in the same class, 0 when edges ignore the feature, negative when they prefer opposite classes.
+pub fn feature_homophily(
+    graph: &HashMap<usize, HashSet<usize>>,
+    features: &HashMap<usize, Vec<usize>>,
+) -> Vec<(usize, usize, f64, f64)> {
+    let mut holders: HashMap<usize, usize> = HashMap::new();
+    for (node, active) in features {
+        if graph.contains_key(node) {
+            for &feature in active {
+                *holders.entry(feature).or_insert(0) += 1;
+            }
+        }
+    }
+
+    // Count edge ends (each undirected edge is seen from both sides): ends at a holder, and ends at a holder whose neighbor also holds the feature
+    let mut holder_ends: HashMap<usize, f64> = HashMap::new();
+    let mut shared_ends: HashMap<usize, f64> = HashMap::new();
+    let mut total_ends = 0.0;
+    for (node, neighbors) in graph {
+        let Some(node_features) = features.get(node) else {
+            continue;
+        };
+        for neighbor in neighbors {
+            let Some(neighbor_features) = features.get(neighbor) else {
+                continue;
+            };
+            total_ends += 1.0;
+            for feature in node_features {
+                *holder_ends.entry(*feature).or_insert(0.0) += 1.0;
+                if neighbor_features.binary_search(feature).is_ok() {
+                    *shared_ends.entry(*feature).or_insert(0.0) += 1.0;
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<(usize, usize, f64, f64)> = holders
+        .into_iter()
+        .map(|(feature, count)| {
+            let ends = holder_ends.get(&feature).copied().unwrap_or(0.0);
+            let shared = shared_ends.get(&feature).copied().unwrap_or(0.0);
+            let homophily = if ends > 0.0 { shared / ends } else { 0.0 };
+
+            // Mixing matrix of the two classes as fractions of edge ends
+            let (both, one_side) = if total_ends > 0.0 { (shared / total_ends, (ends - shared) / total_ends) } else { (0.0, 0.0) };
+            let neither = 1.0 - both - 2.0 * one_side;
+            let with = both + one_side;
+            let expected = with * with + (1.0 - with) * (1.0 - with);
+            let assortativity = if expected < 1.0 { (both + neither - expected) / (1.0 - expected) } else { 0.0 };
+
+            (feature, count, homophily, assortativity)
+        })
+        .collect();
+    results.sort_by_key(|&(feature, _, _, _)| feature);
+
+    results
+}
@@ -0,0 +1,1283 @@
+// Command-line mode. Running the program with arguments skips the interactive prompts and runs a single query against the full dataset, e.g. `cargo run --release -- path 214328887 34428380`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use rand::rngs::StdRng;
+use crate::{analysis, attributes, cliques, community, connectivity, degree, diffusion, directed, epidemic, evaluation, generators, graph, influence, motifs, nullmodel, paths, robustness, snap, spectral, DATA_FILE};
+
+// Defaults for the community detection commands (FLUID_COMMUNITIES is also the number of spectral clusters)
+const FLUID_COMMUNITIES: usize = 20;
+const MAX_ITERATIONS: usize = 100;
+const CLIQUE_SIZE: usize = 3;
+const OVERLAPPING_ALGORITHMS: [&str; 2] = ["cpm", "ego-split"];
+
+// Features held by fewer nodes than this are too rare to judge homophily
+const MIN_FEATURE_HOLDERS: usize = 5;
+
+// Degree-preserving randomizations behind the normalized rich-club coefficient, and double edge swaps per edge in each
+const RICH_CLUB_RANDOMIZATIONS: usize = 3;
+const SWAPS_PER_EDGE: usize = 10;
+
+// Randomized graphs behind each motif z-score
+const MOTIF_RANDOMIZATIONS: usize = 10;
+
+// Baseline models: Watts–Strogatz rewiring probability, and BFS sources averaged for the average separation
+const WATTS_STROGATZ_REWIRING: f64 = 0.1;
+const SEPARATION_SOURCES: usize = 10;
+const BASELINE_MODELS: [&str; 7] = ["gnp", "gnm", "ba", "ws", "chung-lu", "config", "sbm"];
+
+// Randomized copies compared against in the null-model report
+const NULL_MODEL_COPIES: usize = 10;
+
+// Monte Carlo runs per spread estimate, and the independent cascade probability when none is given
+const CASCADE_RUNS: usize = 1000;
+const CASCADE_PROBABILITY: f64 = 0.01;
+
+// Influence maximization: CELF only considers this many accounts with the most followers, IMM's accuracy, and PageRank settings for the comparison
+const CELF_CANDIDATES: usize = 200;
+const IMM_EPSILON: f64 = 0.1;
+const PAGERANK_DAMPING: f64 = 0.85;
+const PAGERANK_ITERATIONS: usize = 100;
+
+// Epidemic simulation defaults: runs, the step or time limit, and how often continuous-time runs are recorded
+const EPIDEMIC_RUNS: usize = 10;
+const EPIDEMIC_MAX_STEPS: usize = 1000;
+const EPIDEMIC_MAX_TIME: f64 = 1000.0;
+const EPIDEMIC_REPORT_INTERVAL: f64 = 1.0;
+
+// Robustness analysis: removal steps along the percolation curve, and sampled sources for betweenness rankings
+const ROBUSTNESS_STEPS: usize = 20;
+const BETWEENNESS_SAMPLES: usize = 100;
+const REMOVAL_STRATEGIES: [&str; 4] = ["random", "degree", "betweenness", "pagerank"];
+
+// Eigenvalues listed at each end of the spectrum
+const SPECTRUM_EIGENVALUES: usize = 5;
+
+// Print the list of available commands
+fn print_usage() {
+    println!("Usage: NTFinal [command]");
+    println!("Run without a command for the interactive analysis. Commands:");
+    println!("  path <a> <b> [--all | --count]   shortest path between two users (--all lists every shortest path, --count only counts them)");
+    println!("  ego <id> [radius]                nodes within radius hops of a user (default 1) and statistics of its ego network");
+    println!("  communities [louvain | leiden | lpa | lpa-sync | fluid | spectral] [resolution | k] [seed]");
+    println!("                                   detect communities (default louvain, resolution 1.0; fluid and spectral take the number of communities k) and list the largest");
+    println!("  overlapping [cpm | ego-split] [k | resolution] [seed]");
+    println!("                                   overlapping communities by k-clique percolation (default k 3) or ego-splitting");
+    println!("  evaluate <ego-prefix> [algorithm] [resolution | k] [seed]");
+    println!("                                   score communities detected in <ego-prefix>.edges (any partitioning or overlapping algorithm) against the circles in <ego-prefix>.circles");
+    println!("  homophily <ego-prefix> [min-holders]");
+    println!("                                   assortativity of each hashtag/mention feature in <ego-prefix>.feat over the ego network's edges");
+    println!("  egos <dir>                       statistics of every ego network (<ego>.edges file) in a SNAP directory");
+    println!("  cliques [id]                     maximal clique size distribution and maximum clique (only cliques containing the user if given)");
+    println!("  degrees [csv-prefix]             in-, out- and total degree distributions with power-law fits (writes <csv-prefix>_<kind>.csv histograms if given)");
+    println!("  assortativity [seed]             degree assortativity (undirected and all four in/out combinations), k_nn(k) and the normalized rich-club coefficient");
+    println!("  reciprocity [id]                 share of follows that are mutual, overall and per user (for the user if given)");
+    println!("  triads                           directed triad census: number of triples of users in each of the 16 follow patterns");
+    println!("  motifs [3 | 4] [directed | undirected] [sample-size] [seed]");
+    println!("                                   counts and z-scores of 3- or 4-node motifs (default 3, directed) against degree-preserving random graphs,");
+    println!("                                   on the subgraph induced by sample-size random users if given");
+    println!("  baseline [gnp | gnm | ba | ws | chung-lu | config | sbm | all] [seed]");
+    println!("                                   compare the graph's statistics with random graphs of the same size, degrees or communities (default all)");
+    println!("  nullmodel [copies] [undirected | directed] [seed]");
+    println!("                                   z-scores of clustering, path length, assortativity and modularity against degree-preserving randomized copies");
+    println!("                                   (default 10; directed swaps keep in- and out-degrees)");
+    println!("  cascade <ic[:p] | wc | lt[:threshold]> <id,id,... | topK> [runs] [seed]");
+    println!("                                   expected spread of a tweet from the seed users (or the K users with most followers) and its spread over time,");
+    println!("                                   under the independent cascade (p default 0.01), weighted cascade or linear threshold model (random thresholds by default)");
+    println!("  influence <ic[:p] | wc | lt[:threshold]> <k> [imm | celf | celf++] [runs] [seed]");
+    println!("                                   choose k seed users to maximize expected spread (default imm) and compare with the top-k users by followers and PageRank");
+    println!("  epidemic <sir | sis> <infection-rate> <recovery-rate> <id,id,... | topN | randomN> [discrete | gillespie] [runs] [csv-path] [seed]");
+    println!("                                   epidemic spread from the initially infected users to their followers (default discrete time, 10 runs);");
+    println!("                                   writes every run's S/I/R time series to csv-path if given");
+    println!("  robustness [random | degree | betweenness | pagerank | all] [static | adaptive] [steps] [seed]");
+    println!("                                   giant component and path length as users are removed (default all strategies, static ranking, 20 steps),");
+    println!("                                   with the robustness index R");
+    println!("  connectivity [id]                articulation points, bridges and biconnected components (whether the user is an articulation point if given)");
+    println!("  spectrum [k] [seed]              top k adjacency and bottom k normalized Laplacian eigenvalues (default 5), spectral gap and Fiedler cut");
+    println!("  eccentricity [id]                radius, diameter, center and periphery of each connected component (the user's eccentricity if given)");
+}
+
+// RUN COMMAND. Dispatch on the first argument
+pub fn run(args: &[String]) {
+    match args[0].as_str() {
+        "path" => path_command(&args[1..]),
+        "ego" => ego_command(&args[1..]),
+        "communities" => communities_command(&args[1..]),
+        "overlapping" => overlapping_command(&args[1..]),
+        "evaluate" => evaluate_command(&args[1..]),
+        "homophily" => homophily_command(&args[1..]),
+        "egos" => egos_command(&args[1..]),
+        "cliques" => cliques_command(&args[1..]),
+        "degrees" => degrees_command(&args[1..]),
+        "assortativity" => assortativity_command(&args[1..]),
+        "reciprocity" => reciprocity_command(&args[1..]),
+        "triads" => triads_command(),
+        "motifs" => motifs_command(&args[1..]),
+        "baseline" => baseline_command(&args[1..]),
+        "nullmodel" => null_model_command(&args[1..]),
+        "cascade" => cascade_command(&args[1..]),
+        "influence" => influence_command(&args[1..]),
+        "epidemic" => epidemic_command(&args[1..]),
+        "robustness" => robustness_command(&args[1..]),
+        "connectivity" => connectivity_command(&args[1..]),
+        "eccentricity" => eccentricity_command(&args[1..]),
+        "spectrum" => spectrum_command(&args[1..]),
+        _ => print_usage(),
+    }
+}
+
+// Parse a positional argument as a node ID
+fn parse_node(arg: Option<&String>) -> Option<usize> {
+    arg.and_then(|a| a.parse::<usize>().ok())
+}
+
+// PATH. Shortest path (or all shortest paths) between two users
+fn path_command(args: &[String]) {
+    let (Some(source), Some(target)) = (parse_node(args.first()), parse_node(args.get(1))) else {
+        println!("Invalid input. Please provide two valid numeric Node IDs.");
+        print_usage();
+        return;
+    };
+    let mode = args.get(2).map(String::as_str);
+
+    let graph = graph::read_graph(DATA_FILE);
+    for node in [source, target] {
+        if !graph.contains_key(&node) {
+            println!("Node {} is not in the graph.", node);
+            return;
+        }
+    }
+
+    match mode {
+        Some("--count") => {
+            let count = paths::count_shortest_paths(&graph, source, target);
+            println!("Number of shortest paths from {} to {}: {}", source, target, count);
+        }
+        Some("--all") => {
+            let all_paths = paths::all_shortest_paths(&graph, source, target);
+            if all_paths.is_empty() {
+                println!("Node {} and Node {} are not connected.", source, target);
+                return;
+            }
+            println!("{} shortest paths from {} to {} ({} hops):", all_paths.len(), source, target, all_paths[0].len() - 1);
+            for path in all_paths {
+                println!("{}", format_path(&path));
+            }
+        }
+        _ => match paths::shortest_path(&graph, source, target) {
+            Some(path) => {
+                println!("Shortest path from {} to {} ({} hops):", source, target, path.len() - 1);
+                println!("{}", format_path(&path));
+            }
+            None => println!("Node {} and Node {} are not connected.", source, target),
+        },
+    }
+}
+
+fn format_path(path: &[usize]) -> String {
+    path.iter().map(|node| node.to_string()).collect::<Vec<_>>().join(" -> ")
+}
+
+// EGO. k-hop neighborhood of a user and the standard statistics of its ego network
+fn ego_command(args: &[String]) {
+    let Some(node) = parse_node(args.first()) else {
+        println!("Invalid input. Please provide a valid numeric Node ID.");
+        print_usage();
+        return;
+    };
+    let radius = args.get(1).and_then(|r| r.parse::<usize>().ok()).unwrap_or(1);
+
+    let graph = graph::read_graph(DATA_FILE);
+    if !graph.contains_key(&node) {
+        println!("Node {} is not in the graph.", node);
+        return;
+    }
+
+    // Number of nodes at each distance
+    let distances = graph::k_hop_neighborhood(&graph, node, radius);
+    let mut per_distance: BTreeMap<usize, usize> = BTreeMap::new();
+    for &distance in distances.values() {
+        *per_distance.entry(distance).or_insert(0) += 1;
+    }
+    println!("Nodes within {} hops of Node {}: {}", radius, node, distances.len());
+    for (distance, count) in &per_distance {
+        println!("Distance {}: {} nodes", distance, count);
+    }
+
+    let (ego_graph, _) = graph::ego_network(&graph, node, radius);
+    if ego_graph.is_empty() {
+        return;
+    }
+    let (num_nodes, num_edges, avg_degree, avg_sep) = graph::analyze_graph(&ego_graph);
+    println!("Ego network - Number of nodes: {}", num_nodes);
+    println!("Ego network - Number of edges: {}", num_edges / 2);
+    println!("Ego network - Average degree: {:.2}", avg_degree);
+    println!("Ego network - Average degrees of separation: {:.2}", avg_sep);
+
+    let mut degree_vec: Vec<_> = graph::degree_centrality(&ego_graph).into_iter().collect();
+    degree_vec.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    println!("\nTop 5 Profiles in the Ego Network (Highest Degree):");
+    for (id, degree) in degree_vec.iter().take(5) {
+        println!("Node ID: {} - Degree: {}", id, degree);
+    }
+}
+
+// COMMUNITIES. Run a community detection algorithm and print the largest communities with their top accounts by degree
+fn communities_command(args: &[String]) {
+    let algorithm = args.first().map_or("louvain", String::as_str);
+    let parameter = args.get(1).map(String::as_str);
+    let seed = args.get(2).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let graph = graph::read_graph(DATA_FILE);
+    let Some(communities) = detect_communities(&graph, algorithm, parameter, &mut rng) else {
+        return;
+    };
+    print_communities(&graph, &communities, modularity_resolution(algorithm, parameter));
+}
+
+// Run one of the community detection algorithms by name. The parameter is the resolution for louvain and leiden and the number of communities for fluid
+// and spectral
+fn detect_communities(
+    graph: &HashMap<usize, HashSet<usize>>,
+    algorithm: &str,
+    parameter: Option<&str>,
+    rng: &mut StdRng,
+) -> Option<HashMap<usize, usize>> {
+    let resolution = modularity_resolution(algorithm, parameter);
+    let k = parameter.and_then(|k| k.parse::<usize>().ok()).unwrap_or(FLUID_COMMUNITIES);
+    let communities = match algorithm {
+        "louvain" => community::louvain(graph, resolution, rng).0,
+        "leiden" => community::leiden(graph, resolution, rng).0,
+        "lpa" => community::label_propagation(graph, false, MAX_ITERATIONS, rng),
+        "lpa-sync" => community::label_propagation(graph, true, MAX_ITERATIONS, rng),
+        "fluid" => community::fluid_communities(graph, k, MAX_ITERATIONS, rng),
+        "spectral" => spectral::spectral_clustering(graph, k, rng),
+        _ => {
+            println!("Unknown community detection algorithm: {}", algorithm);
+            print_usage();
+            return None;
+        }
+    };
+    Some(communities)
+}
+
+// Resolution to score modularity with: the one the algorithm optimized, or the standard 1.0
+fn modularity_resolution(algorithm: &str, parameter: Option<&str>) -> f64 {
+    match algorithm {
+        "louvain" | "leiden" => parameter.and_then(|r| r.parse::<f64>().ok()).unwrap_or(1.0),
+        _ => 1.0,
+    }
+}
+
+// Summary shared by the community detection commands, scoring every algorithm with the same modularity function so they can be compared
+fn print_communities(
+    graph: &HashMap<usize, HashSet<usize>>,
+    communities: &HashMap<usize, usize>,
+    resolution: f64,
+) {
+    let count = communities.values().collect::<HashSet<_>>().len();
+    println!("Number of communities: {}", count);
+    println!("Modularity: {:.4}", community::modularity(graph, communities, resolution));
+
+    println!("\nLargest 10 Communities:");
+    for (id, members) in community::largest_communities(graph, communities, 10) {
+        let top: Vec<String> = members.iter().take(5).map(|m| m.to_string()).collect();
+        println!("Community {} - Size: {} - Top accounts by degree: {}", id, members.len(), top.join(", "));
+    }
+}
+
+// EVALUATE. Detect communities in one SNAP ego network and compare them with its ground-truth circles
+fn evaluate_command(args: &[String]) {
+    let Some(prefix) = args.first() else {
+        print_usage();
+        return;
+    };
+    let algorithm = args.get(1).map_or("louvain", String::as_str);
+    let circles = snap::read_circles(&format!("{}.circles", prefix));
+    // Fluid communities and spectral clustering default to one community per circle
+    let circle_count = circles.len().to_string();
+    let parameter = match args.get(2) {
+        Some(parameter) => Some(parameter.as_str()),
+        None if matches!(algorithm, "fluid" | "spectral") => Some(circle_count.as_str()),
+        None => None,
+    };
+    let seed = args.get(3).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let graph = snap::read_ego_network(prefix);
+    if graph.is_empty() || circles.is_empty() {
+        println!("Could not read an ego network and its circles from {}.edges and {}.circles", prefix, prefix);
+        return;
+    }
+    // Overlapping covers are scored as they are; NMI and the adjusted Rand index need a partition, so each node then counts in its largest community
+    let detected_communities = if OVERLAPPING_ALGORITHMS.contains(&algorithm) {
+        let Some(cover) = detect_overlapping(&graph, algorithm, parameter, &mut rng) else {
+            return;
+        };
+        evaluation::cover_to_communities(&cover)
+    } else {
+        let Some(partition) = detect_communities(&graph, algorithm, parameter, &mut rng) else {
+            return;
+        };
+        evaluation::partition_to_communities(&partition)
+    };
+    let detected = evaluation::communities_to_partition(&detected_communities);
+
+    // Only score the nodes that appear in the ego network
+    let nodes: HashSet<usize> = graph.keys().cloned().collect();
+    let truth: Vec<HashSet<usize>> = circles
+        .iter()
+        .map(|(_, members)| members.intersection(&nodes).cloned().collect::<HashSet<usize>>())
+        .filter(|members| !members.is_empty())
+        .collect();
+    let truth_partition = evaluation::communities_to_partition(&truth);
+
+    println!("Ego network: {} nodes, {} circles, {} detected communities", nodes.len(), truth.len(), detected_communities.len());
+    println!("NMI: {:.4}", evaluation::normalized_mutual_information(&detected, &truth_partition));
+    println!("Adjusted Rand index: {:.4}", evaluation::adjusted_rand_index(&detected, &truth_partition));
+    println!("Best-match F1: {:.4}", evaluation::best_match_f1(&detected_communities, &truth));
+    println!("Omega index: {:.4}", evaluation::omega_index(&detected_communities, &truth, &nodes));
+}
+
+// OVERLAPPING. Detect overlapping communities, where one account can belong to several interest circles
+fn overlapping_command(args: &[String]) {
+    let algorithm = args.first().map_or("cpm", String::as_str);
+    let parameter = args.get(1).map(String::as_str);
+    let seed = args.get(2).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let graph = graph::read_graph(DATA_FILE);
+    let Some(cover) = detect_overlapping(&graph, algorithm, parameter, &mut rng) else {
+        return;
+    };
+    let communities = evaluation::cover_to_communities(&cover);
+    let memberships: usize = cover.values().map(Vec::len).sum();
+
+    println!("Number of communities: {}", communities.len());
+    println!("Nodes in at least one community: {} of {}", cover.len(), graph.len());
+    println!("Average communities per covered node: {:.2}", memberships as f64 / cover.len().max(1) as f64);
+    println!("Nodes in more than one community: {}", cover.values().filter(|c| c.len() > 1).count());
+
+    let degree = |node: &usize| graph.get(node).map_or(0, |n| n.len());
+    let mut largest: Vec<(usize, Vec<usize>)> = communities
+        .into_iter()
+        .enumerate()
+        .map(|(id, members)| {
+            let mut members: Vec<usize> = members.into_iter().collect();
+            members.sort_by(|a, b| degree(b).cmp(&degree(a)).then(a.cmp(b)));
+            (id, members)
+        })
+        .collect();
+    largest.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+
+    println!("\nLargest 10 Communities:");
+    for (id, members) in largest.iter().take(10) {
+        let top: Vec<String> = members.iter().take(5).map(|m| m.to_string()).collect();
+        println!("Community {} - Size: {} - Top accounts by degree: {}", id, members.len(), top.join(", "));
+    }
+}
+
+// Run one of the overlapping community algorithms by name. The parameter is the clique size k for cpm and the Louvain resolution for ego-split
+fn detect_overlapping(
+    graph: &HashMap<usize, HashSet<usize>>,
+    algorithm: &str,
+    parameter: Option<&str>,
+    rng: &mut StdRng,
+) -> Option<HashMap<usize, Vec<usize>>> {
+    match algorithm {
+        "cpm" => {
+            let k = parameter.and_then(|k| k.parse::<usize>().ok()).unwrap_or(CLIQUE_SIZE);
+            Some(community::clique_percolation(graph, k))
+        }
+        "ego-split" => {
+            let resolution = parameter.and_then(|r| r.parse::<f64>().ok()).unwrap_or(1.0);
+            Some(community::ego_splitting(graph, resolution, rng))
+        }
+        _ => {
+            println!("Unknown overlapping community algorithm: {}", algorithm);
+            print_usage();
+            None
+        }
+    }
+}
+
+// HOMOPHILY. Do users who follow each other share hashtags and mentions? Lists the most and least assortative features of one SNAP ego network
+fn homophily_command(args: &[String]) {
+    let Some(prefix) = args.first() else {
+        print_usage();
+        return;
+    };
+    let min_holders = args.get(1).and_then(|m| m.parse::<usize>().ok()).unwrap_or(MIN_FEATURE_HOLDERS);
+
+    let graph = snap::read_ego_network(prefix);
+    let (names, features) = snap::read_features(prefix);
+    if graph.is_empty() || features.is_empty() {
+        println!("Could not read an ego network and its features from {}.edges and {}.feat", prefix, prefix);
+        return;
+    }
+
+    let mut results: Vec<(usize, usize, f64, f64)> = attributes::feature_homophily(&graph, &features)
+        .into_iter()
+        .filter(|&(_, holders, _, _)| holders >= min_holders)
+        .collect();
+    if results.is_empty() {
+        println!("No feature is held by at least {} nodes.", min_holders);
+        return;
+    }
+    results.sort_by(|a, b| b.3.total_cmp(&a.3).then(a.0.cmp(&b.0)));
+
+    let positive = results.iter().filter(|r| r.3 > 0.0).count();
+    let mean: f64 = results.iter().map(|r| r.3).sum::<f64>() / results.len() as f64;
+    println!("Features held by at least {} nodes: {}", min_holders, results.len());
+    println!("Features with positive assortativity: {}", positive);
+    println!("Mean assortativity: {:.4}", mean);
+
+    let name = |feature: usize| names.get(feature).filter(|n| !n.is_empty()).cloned().unwrap_or_else(|| feature.to_string());
+    println!("\nMost Assortative Features:");
+    for &(feature, holders, homophily, assortativity) in results.iter().take(10) {
+        println!("{} - Holders: {} - Homophily: {:.3} - Assortativity: {:.4}", name(feature), holders, homophily, assortativity);
+    }
+    if results.len() > 10 {
+        println!("\nLeast Assortative Features:");
+        for &(feature, holders, homophily, assortativity) in results[10..].iter().rev().take(5) {
+            println!("{} - Holders: {} - Homophily: {:.3} - Assortativity: {:.4}", name(feature), holders, homophily, assortativity);
+        }
+    }
+}
+
+// EGOS. Run the standard graph analysis on each ego network in a SNAP directory and print one summary row per ego
+fn egos_command(args: &[String]) {
+    let Some(dir) = args.first() else {
+        print_usage();
+        return;
+    };
+    let prefixes = snap::ego_prefixes(dir);
+    if prefixes.is_empty() {
+        println!("No <ego>.edges files found in {}", dir);
+        return;
+    }
+
+    println!("{:>20} {:>8} {:>8} {:>10} {:>12}", "Ego", "Nodes", "Edges", "Avg deg", "Avg sep");
+    let mut analyzed = 0;
+    let (mut total_nodes, mut total_degree, mut total_sep) = (0, 0.0, 0.0);
+    for (ego, prefix) in prefixes {
+        let ego_graph = snap::read_ego_network(&prefix);
+        if ego_graph.is_empty() {
+            continue;
+        }
+        let (num_nodes, num_edges, avg_degree, avg_sep) = graph::analyze_graph(&ego_graph);
+        println!("{:>20} {:>8} {:>8} {:>10.2} {:>12.2}", ego, num_nodes, num_edges / 2, avg_degree, avg_sep);
+
+        analyzed += 1;
+        total_nodes += num_nodes;
+        total_degree += avg_degree;
+        total_sep += avg_sep;
+    }
+
+    if analyzed > 0 {
+        println!("\nEgo networks analyzed: {}", analyzed);
+        println!("Mean nodes per ego network: {:.1}", total_nodes as f64 / analyzed as f64);
+        println!("Mean average degree: {:.2}", total_degree / analyzed as f64);
+        println!("Mean average degrees of separation: {:.2}", total_sep / analyzed as f64);
+    }
+}
+
+// CLIQUES. Enumerate maximal cliques, either across the whole graph or only those containing one user
+fn cliques_command(args: &[String]) {
+    let graph = graph::read_graph(DATA_FILE);
+
+    let (distribution, maximum) = match args.first() {
+        Some(arg) => {
+            let Some(node) = parse_node(Some(arg)) else {
+                println!("Invalid input. Please provide a valid numeric Node ID.");
+                return;
+            };
+            if !graph.contains_key(&node) {
+                println!("Node {} is not in the graph.", node);
+                return;
+            }
+            let found = cliques::cliques_containing(&graph, node);
+            let mut distribution = BTreeMap::new();
+            for clique in &found {
+                *distribution.entry(clique.len()).or_insert(0) += 1;
+            }
+            let maximum = found.into_iter().max_by_key(|clique| clique.len()).unwrap_or_default();
+            println!("Maximal cliques containing Node {}:", node);
+            (distribution, maximum)
+        }
+        None => {
+            println!("Maximal cliques in the graph:");
+            cliques::clique_size_distribution(&graph)
+        }
+    };
+
+    let total: usize = distribution.values().sum();
+    println!("Number of maximal cliques: {}", total);
+    for (size, count) in &distribution {
+        println!("Size {}: {} cliques", size, count);
+    }
+    let members: Vec<String> = maximum.iter().map(|node| node.to_string()).collect();
+    println!("Maximum clique ({} accounts): {}", maximum.len(), members.join(", "));
+}
+
+// DEGREES. Histogram, CCDF and power-law fit of the in-, out- and total degree distributions of the directed follow graph
+fn degrees_command(args: &[String]) {
+    let csv_prefix = args.first();
+    let graph = graph::read_directed_graph(DATA_FILE);
+    let out_degrees = graph::degree_centrality(&graph);
+    let in_degrees = graph::degree_centrality(&graph::reverse_graph(&graph));
+
+    let mut nodes: Vec<usize> = graph.keys().cloned().collect();
+    nodes.sort_unstable();
+    let degree_of = |degrees: &HashMap<usize, usize>, node: &usize| degrees.get(node).copied().unwrap_or(0);
+    let kinds: [(&str, Vec<usize>); 3] = [
+        ("in", nodes.iter().map(|n| degree_of(&in_degrees, n)).collect()),
+        ("out", nodes.iter().map(|n| degree_of(&out_degrees, n)).collect()),
+        ("total", nodes.iter().map(|n| degree_of(&in_degrees, n) + degree_of(&out_degrees, n)).collect()),
+    ];
+
+    for (kind, degrees) in &kinds {
+        let mean = degrees.iter().sum::<usize>() as f64 / degrees.len().max(1) as f64;
+        println!("\n{}-degree: mean {:.2}, max {}", kind, mean, degrees.iter().max().unwrap_or(&0));
+
+        match degree::fit_power_law(degrees) {
+            Some(fit) => {
+                println!("Power-law fit: alpha = {:.3}, xmin = {}, KS = {:.4}, tail = {} nodes", fit.alpha, fit.xmin, fit.ks_statistic, fit.tail_size);
+                for (alternative, ratio, p_value) in degree::likelihood_ratio_tests(degrees, &fit) {
+                    let verdict = if p_value >= 0.1 {
+                        "inconclusive"
+                    } else if ratio > 0.0 {
+                        "power law favored"
+                    } else {
+                        "alternative favored"
+                    };
+                    println!("Power law vs {}: R = {:.2}, p = {:.4} ({})", alternative, ratio, p_value, verdict);
+                }
+            }
+            None => println!("Too few nonzero degrees to fit a power law."),
+        }
+
+        if let Some(prefix) = csv_prefix {
+            let path = format!("{}_{}.csv", prefix, kind);
+            match write_degree_csv(&path, degrees) {
+                Ok(()) => println!("Wrote histogram and CCDF to {}", path),
+                Err(e) => println!("Could not write {}: {}", path, e),
+            }
+        }
+    }
+}
+
+// Write degree, count and CCDF columns
+fn write_degree_csv(path: &str, degrees: &[usize]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "degree,count,ccdf")?;
+    let histogram = degree::degree_histogram(degrees);
+    for (degree, ccdf) in degree::degree_ccdf(degrees) {
+        writeln!(writer, "{},{},{}", degree, histogram[&degree], ccdf)?;
+    }
+    writer.flush()
+}
+
+// ASSORTATIVITY. Do big accounts follow other big accounts? Degree correlations on the directed and undirected graphs
+fn assortativity_command(args: &[String]) {
+    let seed = args.first().and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let directed = graph::read_directed_graph(DATA_FILE);
+    let out_degrees = graph::degree_centrality(&directed);
+    let in_degrees = graph::degree_centrality(&graph::reverse_graph(&directed));
+    println!("Directed degree assortativity (follower degree -> followed degree):");
+    for (source_kind, source_degrees) in [("out", &out_degrees), ("in", &in_degrees)] {
+        for (target_kind, target_degrees) in [("out", &out_degrees), ("in", &in_degrees)] {
+            let r = degree::degree_assortativity(&directed, source_degrees, target_degrees);
+            println!("  {}-{}: {:.4}", source_kind, target_kind, r);
+        }
+    }
+
+    let graph = graph::read_graph(DATA_FILE);
+    let degrees = graph::degree_centrality(&graph);
+    println!("Undirected degree assortativity: {:.4}", degree::degree_assortativity(&graph, &degrees, &degrees));
+
+    // k_nn(k) averaged over logarithmic degree bins, weighting each degree by its number of nodes
+    let histogram = degree::degree_histogram(&degrees.values().cloned().collect::<Vec<_>>());
+    let mut bins: BTreeMap<usize, (f64, usize)> = BTreeMap::new();
+    for (k, knn) in degree::average_neighbor_degree(&graph, &degrees, &degrees) {
+        let bin = bins.entry(k.ilog2() as usize).or_insert((0.0, 0));
+        bin.0 += knn * histogram[&k] as f64;
+        bin.1 += histogram[&k];
+    }
+    println!("\nAverage neighbor degree k_nn(k):");
+    for (bin, (total, nodes)) in bins {
+        println!("  k {:>5}-{:<5} k_nn {:>9.2} ({} nodes)", 1usize << bin, (1usize << (bin + 1)) - 1, total / nodes as f64, nodes);
+    }
+
+    println!("\nRich-club coefficient (normalized by {} degree-preserving randomizations):", RICH_CLUB_RANDOMIZATIONS);
+    let rich_club = degree::normalized_rich_club(&graph, RICH_CLUB_RANDOMIZATIONS, SWAPS_PER_EDGE, &mut rng);
+    let mut k = 1;
+    while let Some((&degree, &(coefficient, ratio))) = rich_club.range(k..).next() {
+        println!("  k > {:<5} phi {:.4}, normalized {:.3}", degree, coefficient, ratio);
+        k = (degree * 2).max(degree + 1);
+    }
+}
+
+// RECIPROCITY. How often follows are returned, overall and for each user
+fn reciprocity_command(args: &[String]) {
+    let graph = graph::read_directed_graph(DATA_FILE);
+    println!("Global reciprocity: {:.4}", directed::reciprocity(&graph));
+
+    let per_node = directed::node_reciprocity(&graph);
+    if let Some(node) = parse_node(args.first()) {
+        match per_node.get(&node) {
+            Some(value) => println!("Reciprocity of node {}: {:.4}", node, value),
+            None => println!("Node {} has no follows in the graph.", node),
+        }
+        return;
+    }
+
+    let mut values: Vec<f64> = per_node.values().cloned().collect();
+    if values.is_empty() {
+        return;
+    }
+    values.sort_by(f64::total_cmp);
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    println!("Per-user reciprocity over {} users: mean {:.4}, median {:.4}", values.len(), mean, values[values.len() / 2]);
+    let none = values.iter().filter(|&&v| v == 0.0).count();
+    let all = values.iter().filter(|&&v| v == 1.0).count();
+    println!("Users with no mutual follows: {} ({:.1}%)", none, 100.0 * none as f64 / values.len() as f64);
+    println!("Users whose follows are all mutual: {} ({:.1}%)", all, 100.0 * all as f64 / values.len() as f64);
+}
+
+// TRIADS. Directed triad census, with each class's share of the connected triads
+fn triads_command() {
+    let graph = graph::read_directed_graph(DATA_FILE);
+    let census = directed::triad_census(&graph);
+    let connected: u64 = census[1..].iter().sum();
+
+    println!("{:<6} {:>20} {:>12}", "triad", "count", "% connected");
+    for (name, &count) in directed::TRIAD_NAMES.iter().zip(&census) {
+        if *name == "003" {
+            println!("{:<6} {:>20} {:>12}", name, count, "-");
+        } else {
+            println!("{:<6} {:>20} {:>11.4}%", name, count, 100.0 * count as f64 / connected.max(1) as f64);
+        }
+    }
+
+    // Transitive triads close every two-step follow path; cyclic ones (030C) close it the other way round
+    let transitive = census[8] as f64;
+    let cyclic = census[9] as f64;
+    if transitive + cyclic > 0.0 {
+        println!("Transitive (030T) vs cyclic (030C) closure: {:.1}% transitive", 100.0 * transitive / (transitive + cyclic));
+    }
+}
+
+// MOTIFS. Over- and under-represented 3- or 4-node patterns compared with degree-preserving random graphs
+fn motifs_command(args: &[String]) {
+    let size = match args.first().map(String::as_str) {
+        None | Some("3") => 3,
+        Some("4") => 4,
+        Some(other) => {
+            println!("Motif size must be 3 or 4, not {}.", other);
+            return;
+        }
+    };
+    let directed = match args.get(1).map(String::as_str) {
+        None | Some("directed") => true,
+        Some("undirected") => false,
+        Some(other) => {
+            println!("Unknown mode {}; use directed or undirected.", other);
+            return;
+        }
+    };
+    let sample_size = args.get(2).and_then(|s| s.parse::<usize>().ok());
+    let seed = args.get(3).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut graph = if directed { graph::read_directed_graph(DATA_FILE) } else { graph::read_graph(DATA_FILE) };
+    if let Some(sample_size) = sample_size {
+        let mut nodes: Vec<usize> = graph.keys().cloned().collect();
+        nodes.sort_unstable();
+        let sample: HashSet<usize> = nodes.choose_multiple(&mut rng, sample_size).cloned().collect();
+        graph = graph::induced_subgraph(&graph, &sample);
+        println!("Sampled {} users ({} with edges among them).", sample.len(), graph.len());
+    }
+
+    let mut scores = motifs::motif_significance(&graph, size, directed, MOTIF_RANDOMIZATIONS, SWAPS_PER_EDGE, &mut rng);
+    scores.sort_by(|a, b| b.z_score.total_cmp(&a.z_score));
+    println!(
+        "{} {}-node motifs against {} randomizations:",
+        if directed { "Directed" } else { "Undirected" },
+        size,
+        MOTIF_RANDOMIZATIONS
+    );
+    println!("{:>12} {:>14} {:>10} {:>9}  edges", "count", "random mean", "random sd", "z");
+    for score in scores {
+        println!(
+            "{:>12} {:>14.1} {:>10.1} {:>9.2}  {}",
+            score.count,
+            score.random_mean,
+            score.random_std,
+            score.z_score,
+            motifs::describe_motif(score.code, size, directed)
+        );
+    }
+}
+
+// BASELINE. The Twitter graph's statistics next to random graphs matched to it: G(n, p) and G(n, m) with the same size, Barabási–Albert and
+// Watts–Strogatz with the same average degree, Chung–Lu and the configuration model with the same degrees, and a stochastic block model
+// with the Louvain communities as blocks
+fn baseline_command(args: &[String]) {
+    let model = args.first().map_or("all", String::as_str);
+    if model != "all" && !BASELINE_MODELS.contains(&model) {
+        println!("Unknown model: {}", model);
+        print_usage();
+        return;
+    }
+    let seed = args.get(1).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let graph = graph::read_graph(DATA_FILE);
+    let mut nodes: Vec<usize> = graph.keys().cloned().collect();
+    nodes.sort_unstable();
+    let degrees: Vec<usize> = nodes.iter().map(|node| graph[node].len()).collect();
+    let n = nodes.len();
+    let m = degrees.iter().sum::<usize>() / 2;
+    let mean_degree = 2.0 * m as f64 / n.max(1) as f64;
+
+    println!("{:<10} {:>9} {:>10} {:>9} {:>9} {:>14} {:>11}", "graph", "nodes", "edges", "mean deg", "max deg", "assortativity", "separation");
+    print_baseline_row("twitter", &graph, &mut rng);
+
+    for name in BASELINE_MODELS.iter().filter(|&&name| model == "all" || model == name) {
+        let generated = match *name {
+            "gnp" => generators::erdos_renyi_gnp(n, 2.0 * m as f64 / (n as f64 * (n as f64 - 1.0)).max(1.0), &mut rng),
+            "gnm" => generators::erdos_renyi_gnm(n, m, &mut rng),
+            "ba" => generators::barabasi_albert(n, (mean_degree / 2.0).round().max(1.0) as usize, &mut rng),
+            "ws" => generators::watts_strogatz(n, 2 * (mean_degree / 2.0).round() as usize, WATTS_STROGATZ_REWIRING, &mut rng),
+            "chung-lu" => generators::chung_lu(&degrees.iter().map(|&d| d as f64).collect::<Vec<_>>(), &mut rng),
+            "config" => generators::configuration_model(&degrees, &mut rng),
+            _ => {
+                let (sizes, probabilities) = block_model_parameters(&graph, &mut rng);
+                generators::stochastic_block_model(&sizes, &probabilities, &mut rng)
+            }
+        };
+        print_baseline_row(name, &generated, &mut rng);
+    }
+}
+
+// Block sizes and edge densities within and between the graph's Louvain communities
+fn block_model_parameters(graph: &HashMap<usize, HashSet<usize>>, rng: &mut StdRng) -> (Vec<usize>, Vec<Vec<f64>>) {
+    let (communities, _) = community::louvain(graph, 1.0, rng);
+    let blocks = communities.values().max().map_or(0, |&c| c + 1);
+    let mut sizes = vec![0usize; blocks];
+    for &c in communities.values() {
+        sizes[c] += 1;
+    }
+
+    // Edges are seen from both ends, so within a block both the links and the pairs are counted as ordered pairs
+    let mut edges = vec![vec![0usize; blocks]; blocks];
+    for (node, neighbors) in graph {
+        for neighbor in neighbors {
+            edges[communities[node]][communities[neighbor]] += 1;
+        }
+    }
+    let probabilities = (0..blocks)
+        .map(|i| {
+            (0..blocks)
+                .map(|j| {
+                    let pairs = if i == j { sizes[i] * sizes[i].saturating_sub(1) } else { sizes[i] * sizes[j] };
+                    let links = if i == j { edges[i][i] } else { edges[i][j] };
+                    if pairs == 0 { 0.0 } else { links as f64 / pairs as f64 }
+                })
+                .collect()
+        })
+        .collect();
+    (sizes, probabilities)
+}
+
+// One row of the baseline table. Separation is the mean distance to the other reachable nodes, averaged over a few random non-isolated sources
+fn print_baseline_row(name: &str, graph: &HashMap<usize, HashSet<usize>>, rng: &mut StdRng) {
+    let degrees = graph::degree_centrality(graph);
+    let edges = degrees.values().sum::<usize>() / 2;
+    let mut connected: Vec<usize> = graph.iter().filter(|(_, neighbors)| !neighbors.is_empty()).map(|(&node, _)| node).collect();
+    connected.sort_unstable();
+    let sources: Vec<usize> = connected.choose_multiple(rng, SEPARATION_SOURCES).cloned().collect();
+    let separation = sources.iter().map(|&node| graph::mean_distance(graph, node)).sum::<f64>() / sources.len().max(1) as f64;
+
+    println!(
+        "{:<10} {:>9} {:>10} {:>9.2} {:>9} {:>14.4} {:>11.3}",
+        name,
+        graph.len(),
+        edges,
+        2.0 * edges as f64 / graph.len().max(1) as f64,
+        degrees.values().max().unwrap_or(&0),
+        degree::degree_assortativity(graph, &degrees, &degrees),
+        separation
+    );
+}
+
+// NULL MODEL. How far the graph's clustering, average path length, degree assortativity and modularity sit from degree-preserving randomized
+// copies of it, in standard deviations of the copies
+fn null_model_command(args: &[String]) {
+    let copies = args.first().and_then(|s| s.parse::<usize>().ok()).unwrap_or(NULL_MODEL_COPIES).max(2);
+    let directed = match args.get(1).map(String::as_str) {
+        None | Some("undirected") => false,
+        Some("directed") => true,
+        Some(other) => {
+            println!("Unknown mode {}; use directed or undirected.", other);
+            return;
+        }
+    };
+    let seed = args.get(2).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let graph = if directed { graph::read_directed_graph(DATA_FILE) } else { graph::read_graph(DATA_FILE) };
+    // Path lengths are averaged over BFS from the same nodes in every copy, since swaps never isolate a node
+    let undirected = graph::to_undirected(&graph);
+    let mut connected: Vec<usize> = undirected.iter().filter(|(_, neighbors)| !neighbors.is_empty()).map(|(&node, _)| node).collect();
+    connected.sort_unstable();
+    let sources: Vec<usize> = connected.choose_multiple(&mut rng, SEPARATION_SOURCES).cloned().collect();
+
+    let observed = null_model_statistics(&undirected, &sources, &mut rng);
+    let mut samples: Vec<[f64; 4]> = Vec::with_capacity(copies);
+    for copy in 1..=copies {
+        let randomized = nullmodel::randomized_copy(&graph, directed, SWAPS_PER_EDGE, &mut rng);
+        samples.push(null_model_statistics(&graph::to_undirected(&randomized), &sources, &mut rng));
+        println!("Randomized copy {}/{} done", copy, copies);
+    }
+
+    println!(
+        "\n{} degree-preserving copies ({} edge swaps):",
+        copies,
+        if directed { "directed" } else { "undirected" }
+    );
+    println!("{:<22} {:>10} {:>12} {:>10} {:>9}", "statistic", "observed", "random mean", "random sd", "z");
+    let names = ["average clustering", "average path length", "degree assortativity", "modularity (Louvain)"];
+    for (index, name) in names.iter().enumerate() {
+        let mean = samples.iter().map(|s| s[index]).sum::<f64>() / copies as f64;
+        let std = (samples.iter().map(|s| (s[index] - mean).powi(2)).sum::<f64>() / (copies - 1) as f64).sqrt();
+        let z = if std > 0.0 { (observed[index] - mean) / std } else { 0.0 };
+        println!("{:<22} {:>10.4} {:>12.4} {:>10.4} {:>9.2}", name, observed[index], mean, std, z);
+    }
+}
+
+// Average clustering, average path length from the given sources, degree assortativity and Louvain modularity of an undirected graph
+fn null_model_statistics(graph: &HashMap<usize, HashSet<usize>>, sources: &[usize], rng: &mut StdRng) -> [f64; 4] {
+    let degrees = graph::degree_centrality(graph);
+    let path_length = sources.iter().map(|&node| graph::mean_distance(graph, node)).sum::<f64>() / sources.len().max(1) as f64;
+    [
+        analysis::average_clustering(graph),
+        path_length,
+        degree::degree_assortativity(graph, &degrees, &degrees),
+        community::louvain(graph, 1.0, rng).1,
+    ]
+}
+
+// Parse a diffusion model: "ic" or "ic:<p>", "wc", "lt" or "lt:<threshold>"
+fn parse_diffusion_model(arg: &str) -> Option<diffusion::DiffusionModel> {
+    let (name, value) = match arg.split_once(':') {
+        Some((name, value)) => (name, Some(value.parse::<f64>().ok()?)),
+        None => (arg, None),
+    };
+    match name {
+        "ic" => Some(diffusion::DiffusionModel::IndependentCascade(diffusion::EdgeProbability::Uniform(
+            value.unwrap_or(CASCADE_PROBABILITY),
+        ))),
+        "wc" => Some(diffusion::DiffusionModel::IndependentCascade(diffusion::EdgeProbability::WeightedCascade)),
+        "lt" => Some(diffusion::DiffusionModel::LinearThreshold(match value {
+            Some(threshold) => diffusion::Thresholds::Fixed(threshold),
+            None => diffusion::Thresholds::Random,
+        })),
+        _ => None,
+    }
+}
+
+// Accounts with the most followers (highest in-degree in the follow graph), ties broken by ID
+fn top_by_followers(graph: &HashMap<usize, HashSet<usize>>, k: usize) -> Vec<usize> {
+    let mut followers: Vec<(usize, usize)> = graph::degree_centrality(&graph::reverse_graph(graph)).into_iter().collect();
+    followers.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    followers.into_iter().take(k).map(|(node, _)| node).collect()
+}
+
+// CASCADE. Monte Carlo spread of a tweet from a seed set over the follow graph
+fn cascade_command(args: &[String]) {
+    let (Some(model), Some(seed_arg)) = (args.first().and_then(|m| parse_diffusion_model(m)), args.get(1)) else {
+        println!("Invalid input. Please give a diffusion model and a seed set.");
+        print_usage();
+        return;
+    };
+    let runs = args.get(2).and_then(|s| s.parse::<usize>().ok()).unwrap_or(CASCADE_RUNS);
+    let seed = args.get(3).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+
+    let graph = graph::read_directed_graph(DATA_FILE);
+    let seed_nodes: Vec<usize> = match seed_arg.strip_prefix("top").and_then(|k| k.parse::<usize>().ok()) {
+        Some(k) => top_by_followers(&graph, k),
+        None => match seed_arg.split(',').map(|id| id.parse::<usize>()).collect::<Result<Vec<_>, _>>() {
+            Ok(ids) => ids,
+            Err(_) => {
+                println!("Invalid seed set {}: give comma-separated node IDs or topK.", seed_arg);
+                return;
+            }
+        },
+    };
+
+    let diffusion_graph = diffusion::DiffusionGraph::new(&graph);
+    let mut seeds = Vec::new();
+    for &node in &seed_nodes {
+        match diffusion_graph.index_of(node) {
+            Some(index) => seeds.push(index),
+            None => println!("Node {} is not in the graph; skipping it.", node),
+        }
+    }
+    if seeds.is_empty() {
+        return;
+    }
+
+    let estimate = diffusion_graph.expected_spread(model, &seeds, runs, seed);
+    println!(
+        "Seeds: {}",
+        seed_nodes.iter().map(|node| node.to_string()).collect::<Vec<_>>().join(", ")
+    );
+    println!(
+        "Expected spread over {} runs: {:.2} accounts (sd {:.2}, {:.3}% of the graph)",
+        runs,
+        estimate.mean,
+        estimate.std_dev,
+        100.0 * estimate.mean / diffusion_graph.nodes.len() as f64
+    );
+    println!("\n{:>5} {:>14} {:>12}", "step", "mean active", "new");
+    let mut previous = 0.0;
+    for (step, &active) in estimate.curve.iter().enumerate() {
+        println!("{:>5} {:>14.2} {:>12.2}", step, active, active - previous);
+        previous = active;
+    }
+}
+
+// INFLUENCE. Seed accounts chosen for maximum spread, against the obvious choices of the most-followed and highest-PageRank accounts
+fn influence_command(args: &[String]) {
+    let (Some(model), Some(k)) = (
+        args.first().and_then(|m| parse_diffusion_model(m)),
+        args.get(1).and_then(|k| k.parse::<usize>().ok()),
+    ) else {
+        println!("Invalid input. Please give a diffusion model and the number of seeds k.");
+        print_usage();
+        return;
+    };
+    let method = args.get(2).map_or("imm", String::as_str);
+    let runs = args.get(3).and_then(|s| s.parse::<usize>().ok()).unwrap_or(CASCADE_RUNS);
+    let seed = args.get(4).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+
+    let graph = graph::read_directed_graph(DATA_FILE);
+    let diffusion_graph = diffusion::DiffusionGraph::new(&graph);
+    let to_indices = |nodes: &[usize]| nodes.iter().filter_map(|&node| diffusion_graph.index_of(node)).collect::<Vec<_>>();
+
+    let chosen: Vec<usize> = match method {
+        "imm" => match influence::imm(&diffusion_graph, model, k, IMM_EPSILON, seed) {
+            Some((seeds, estimate)) => {
+                println!("IMM estimate from reverse reachable sets: {:.2}", estimate);
+                seeds
+            }
+            None => {
+                println!("IMM needs random thresholds for the linear threshold model; use celf or celf++ instead.");
+                return;
+            }
+        },
+        "celf" | "celf++" => {
+            let candidates = to_indices(&top_by_followers(&graph, CELF_CANDIDATES));
+            println!("Greedy search over the {} accounts with the most followers", candidates.len());
+            influence::greedy_celf(&diffusion_graph, model, &candidates, k, runs, seed, method == "celf++")
+                .into_iter()
+                .map(|(node, _)| node)
+                .collect()
+        }
+        _ => {
+            println!("Unknown method: {}", method);
+            print_usage();
+            return;
+        }
+    };
+
+    let mut by_pagerank: Vec<(usize, f64)> = graph::pagerank(&graph, PAGERANK_DAMPING, PAGERANK_ITERATIONS).into_iter().collect();
+    by_pagerank.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    let by_pagerank: Vec<usize> = by_pagerank.into_iter().take(k).map(|(node, _)| node).collect();
+
+    // Every seed set is scored on the same simulations, with a different random seed from the one used to choose them
+    let evaluation_seed = seed.wrapping_add(1);
+    println!("\nExpected spread over {} runs:", runs);
+    for (name, seeds) in [
+        (method, chosen),
+        ("followers", to_indices(&top_by_followers(&graph, k))),
+        ("pagerank", to_indices(&by_pagerank)),
+    ] {
+        let estimate = diffusion_graph.expected_spread(model, &seeds, runs, evaluation_seed);
+        let ids: Vec<String> = seeds.iter().map(|&index| diffusion_graph.nodes[index].to_string()).collect();
+        println!("{:<10} {:>10.2} (sd {:.2})  {}", name, estimate.mean, estimate.std_dev, ids.join(", "));
+    }
+}
+
+// EPIDEMIC. SIR or SIS runs over the follow network, summarized and optionally written out as S/I/R time series
+fn epidemic_command(args: &[String]) {
+    let model = match args.first().map(String::as_str) {
+        Some("sir") => epidemic::EpidemicModel::Sir,
+        Some("sis") => epidemic::EpidemicModel::Sis,
+        _ => {
+            println!("Invalid input. Please choose the sir or sis model.");
+            print_usage();
+            return;
+        }
+    };
+    let rates = (args.get(1).and_then(|s| s.parse::<f64>().ok()), args.get(2).and_then(|s| s.parse::<f64>().ok()));
+    let ((Some(infection_rate), Some(recovery_rate)), Some(initial_arg)) = (rates, args.get(3)) else {
+        println!("Invalid input. Please give the infection rate, recovery rate and initially infected users.");
+        print_usage();
+        return;
+    };
+    let continuous = match args.get(4).map(String::as_str) {
+        None | Some("discrete") => false,
+        Some("gillespie") => true,
+        Some(other) => {
+            println!("Unknown simulation method {}; use discrete or gillespie.", other);
+            return;
+        }
+    };
+    let runs = args.get(5).and_then(|s| s.parse::<usize>().ok()).unwrap_or(EPIDEMIC_RUNS);
+    let csv_path = args.get(6);
+    let seed = args.get(7).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let graph = graph::read_directed_graph(DATA_FILE);
+    let diffusion_graph = diffusion::DiffusionGraph::new(&graph);
+    let initial: Vec<usize> = if let Some(n) = initial_arg.strip_prefix("top").and_then(|n| n.parse::<usize>().ok()) {
+        top_by_followers(&graph, n).into_iter().filter_map(|node| diffusion_graph.index_of(node)).collect()
+    } else if let Some(n) = initial_arg.strip_prefix("random").and_then(|n| n.parse::<usize>().ok()) {
+        let indices: Vec<usize> = (0..diffusion_graph.nodes.len()).collect();
+        indices.choose_multiple(&mut rng, n).cloned().collect()
+    } else {
+        match initial_arg.split(',').map(|id| id.parse::<usize>()).collect::<Result<Vec<_>, _>>() {
+            Ok(ids) => ids.into_iter().filter_map(|node| diffusion_graph.index_of(node)).collect(),
+            Err(_) => {
+                println!("Invalid initial infected set {}: give comma-separated node IDs, topN or randomN.", initial_arg);
+                return;
+            }
+        }
+    };
+    if initial.is_empty() {
+        println!("None of the initially infected users are in the graph.");
+        return;
+    }
+
+    let settings = epidemic::EpidemicSettings { model, infection_rate, recovery_rate };
+    let all_series: Vec<Vec<(f64, usize, usize, usize)>> = (0..runs)
+        .map(|_| {
+            if continuous {
+                epidemic::simulate_gillespie(&diffusion_graph, settings, &initial, EPIDEMIC_MAX_TIME, EPIDEMIC_REPORT_INTERVAL, &mut rng)
+            } else {
+                epidemic::simulate_discrete(&diffusion_graph, settings, &initial, EPIDEMIC_MAX_STEPS, &mut rng)
+            }
+        })
+        .collect();
+
+    let n = diffusion_graph.nodes.len() as f64;
+    // Sums over runs of the peak, final compartment sizes and duration
+    let (mut peak, mut final_s, mut final_i, mut final_r, mut duration) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for series in &all_series {
+        peak += series.iter().map(|&(_, _, i, _)| i).max().unwrap_or(0) as f64;
+        if let Some(&(time, s, i, r)) = series.last() {
+            final_s += s as f64;
+            final_i += i as f64;
+            final_r += r as f64;
+            duration += time;
+        }
+    }
+    let runs_f = runs.max(1) as f64;
+    let (peak, final_s, final_i, final_r, duration) = (peak / runs_f, final_s / runs_f, final_i / runs_f, final_r / runs_f, duration / runs_f);
+
+    println!("{} initially infected, {} runs ({} time)", initial.len(), runs, if continuous { "continuous" } else { "discrete" });
+    println!("Mean peak infected: {:.1} ({:.2}% of accounts)", peak, 100.0 * peak / n);
+    println!("Mean final state: S {:.1}, I {:.1}, R {:.1}", final_s, final_i, final_r);
+    println!("Mean duration: {:.1} {}", duration, if continuous { "time units" } else { "steps" });
+
+    if let Some(path) = csv_path {
+        match write_epidemic_csv(path, &all_series) {
+            Ok(()) => println!("Wrote time series to {}", path),
+            Err(e) => println!("Could not write {}: {}", path, e),
+        }
+    }
+}
+
+// Write run, time, susceptible, infected and recovered columns
+fn write_epidemic_csv(path: &str, all_series: &[Vec<(f64, usize, usize, usize)>]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "run,time,susceptible,infected,recovered")?;
+    for (run, series) in all_series.iter().enumerate() {
+        for &(time, s, i, r) in series {
+            writeln!(writer, "{},{},{},{},{}", run, time, s, i, r)?;
+        }
+    }
+    writer.flush()
+}
+
+// ROBUSTNESS. Percolation curves and robustness index under random failures and targeted attacks
+fn robustness_command(args: &[String]) {
+    let strategy_arg = args.first().map_or("all", String::as_str);
+    if strategy_arg != "all" && !REMOVAL_STRATEGIES.contains(&strategy_arg) {
+        println!("Unknown removal strategy: {}", strategy_arg);
+        print_usage();
+        return;
+    }
+    let adaptive = match args.get(1).map(String::as_str) {
+        None | Some("static") => false,
+        Some("adaptive") => true,
+        Some(other) => {
+            println!("Unknown ranking mode {}; use static or adaptive.", other);
+            return;
+        }
+    };
+    let steps = args.get(2).and_then(|s| s.parse::<usize>().ok()).unwrap_or(ROBUSTNESS_STEPS);
+    let seed = args.get(3).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let graph = graph::read_graph(DATA_FILE);
+    let mut summary = Vec::new();
+    for name in REMOVAL_STRATEGIES.iter().filter(|&&name| strategy_arg == "all" || strategy_arg == name) {
+        let strategy = match *name {
+            "random" => robustness::RemovalStrategy::Random,
+            "degree" => robustness::RemovalStrategy::Degree,
+            "betweenness" => robustness::RemovalStrategy::Betweenness(BETWEENNESS_SAMPLES),
+            _ => robustness::RemovalStrategy::PageRank,
+        };
+        let curve = robustness::percolation_curve(&graph, strategy, adaptive, steps, &mut rng);
+        let r_index = robustness::robustness_index(&curve);
+
+        println!("\n{} removal ({}):", name, if adaptive && *name != "random" { "adaptive" } else { "static" });
+        println!("{:>9} {:>15} {:>12}", "removed", "giant fraction", "path length");
+        for (removed, giant, path_length) in &curve {
+            println!("{:>8.1}% {:>15.4} {:>12.3}", 100.0 * removed, giant, path_length);
+        }
+        summary.push((name, r_index));
+    }
+
+    println!("\nRobustness index R (0.5 at most):");
+    for (name, r_index) in summary {
+        println!("  {:<12} {:.4}", name, r_index);
+    }
+}
+
+// CONNECTIVITY. Accounts and links whose removal would disconnect part of the network
+fn connectivity_command(args: &[String]) {
+    let graph = graph::read_graph(DATA_FILE);
+    let articulation_points = connectivity::articulation_points(&graph);
+    let bridges = connectivity::bridges(&graph);
+    let components = connectivity::biconnected_components(&graph);
+
+    if let Some(node) = parse_node(args.first()) {
+        if !graph.contains_key(&node) {
+            println!("Node {} is not in the graph.", node);
+            return;
+        }
+        let member_of = components.iter().filter(|component| component.binary_search(&node).is_ok()).count();
+        let node_bridges: Vec<String> = bridges
+            .iter()
+            .filter(|&&(a, b)| a == node || b == node)
+            .map(|(a, b)| format!("{}-{}", a, b))
+            .collect();
+        if articulation_points.binary_search(&node).is_ok() {
+            println!("Node {} is an articulation point: removing it splits its {} biconnected components apart.", node, member_of);
+        } else {
+            println!("Node {} is not an articulation point.", node);
+        }
+        if !node_bridges.is_empty() {
+            println!("Bridges at node {}: {}", node, node_bridges.join(", "));
+        }
+        return;
+    }
+
+    println!("Articulation points: {} of {} nodes", articulation_points.len(), graph.len());
+    println!("Bridges: {}", bridges.len());
+    println!(
+        "Biconnected components: {} (largest {} nodes, {} of them single bridges)",
+        components.len(),
+        components.first().map_or(0, Vec::len),
+        components.iter().filter(|component| component.len() == 2).count()
+    );
+
+    // The articulation points that hold the most pieces together
+    let mut memberships: HashMap<usize, usize> = HashMap::new();
+    for component in &components {
+        for node in component {
+            *memberships.entry(*node).or_insert(0) += 1;
+        }
+    }
+    let mut ranked: Vec<(usize, usize)> = articulation_points.iter().map(|&node| (node, memberships[&node])).collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    if !ranked.is_empty() {
+        println!("\nArticulation points joining the most biconnected components:");
+        for (node, count) in ranked.into_iter().take(10) {
+            println!("  Node {}: {} components, degree {}", node, count, graph[&node].len());
+        }
+    }
+}
+
+// ECCENTRICITY. How far the most remote account is from each account, summarized per connected component
+fn eccentricity_command(args: &[String]) {
+    let graph = graph::read_graph(DATA_FILE);
+
+    if let Some(node) = parse_node(args.first()) {
+        let eccentricities = paths::eccentricities(&graph);
+        match eccentricities.get(&node) {
+            Some(eccentricity) => println!("Node {} has eccentricity {}: every account in its component is within {} hops.", node, eccentricity, eccentricity),
+            None => println!("Node {} is not in the graph.", node),
+        }
+        return;
+    }
+
+    let summaries = paths::eccentricity_summary(&graph);
+    println!("{:>10} {:>7} {:>9} {:>8} {:>10}", "Size", "Radius", "Diameter", "Centers", "Periphery");
+    for summary in summaries.iter().take(10) {
+        println!(
+            "{:>10} {:>7} {:>9} {:>8} {:>10}",
+            summary.size,
+            summary.radius,
+            summary.diameter,
+            summary.center.len(),
+            summary.periphery.len()
+        );
+    }
+    if let Some(giant) = summaries.first() {
+        let preview = |nodes: &[usize]| nodes.iter().take(10).map(|node| node.to_string()).collect::<Vec<_>>().join(", ");
+        println!("Center of the largest component: {}", preview(&giant.center));
+        println!("Periphery of the largest component: {}", preview(&giant.periphery));
+    }
+}
+
+// SPECTRUM. Extreme eigenvalues of the adjacency matrix and normalized Laplacian, and the cut given by the Fiedler vector
+fn spectrum_command(args: &[String]) {
+    let k = args.first().and_then(|s| s.parse::<usize>().ok()).unwrap_or(SPECTRUM_EIGENVALUES);
+    let seed = args.get(1).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let graph = graph::read_graph(DATA_FILE);
+
+    let values = |matrix, largest, rng: &mut StdRng| {
+        let (pairs, converged) = spectral::eigenpairs(&graph, matrix, k, largest, rng);
+        let values = pairs.iter().map(|(value, _)| format!("{:.4}", value)).collect::<Vec<_>>().join(", ");
+        if converged { values } else { format!("{} (not converged)", values) }
+    };
+    println!("Largest adjacency eigenvalues: {}", values(spectral::SpectralMatrix::Adjacency, true, &mut rng));
+    println!("Smallest normalized Laplacian eigenvalues: {}", values(spectral::SpectralMatrix::NormalizedLaplacian, false, &mut rng));
+    println!("Spectral gap (adjacency): {:.4}", spectral::spectral_gap(&graph, &mut rng));
+
+    let Some((connectivity, fiedler)) = spectral::fiedler_vector(&graph, &mut rng) else {
+        return;
+    };
+    println!("Algebraic connectivity of the largest component ({} nodes): {:.6}", fiedler.len(), connectivity);
+    let positive = fiedler.values().filter(|&&x| x >= 0.0).count();
+    let cut = fiedler
+        .iter()
+        .flat_map(|(node, x)| graph[node].iter().map(move |neighbor| (x, neighbor)))
+        .filter(|&(x, neighbor)| fiedler.get(neighbor).is_some_and(|y| (*x >= 0.0) != (*y >= 0.0)))
+        .count()
+        / 2;
+    println!("Fiedler cut: {} and {} nodes, {} edges across", positive, fiedler.len() - positive, cut);
+}
@@ -0,0 +1,154 @@
+// Maximal cliques: groups of accounts that are all connected to each other, with no other account connected to all of them. Tight groups of mutually-following accounts are what the community team looks for.
+// Uses Bron–Kerbosch with pivoting, with the top level in degeneracy order (Eppstein, Löffler and Strash), directly on the adjacency sets from graph.rs.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// DEGENERACY ORDERING. Repeatedly remove the node of smallest remaining degree. Every node has at most d (the graph's degeneracy) neighbors later in the order
+pub fn degeneracy_ordering(graph: &HashMap<usize, HashSet<usize>>) -> Vec<usize> {
+    let mut degrees: HashMap<usize, usize> = graph
+        .iter()
+        .map(|(&node, neighbors)| (node, neighbors.iter().filter(|&&n| n != node).count()))
+        .collect();
+    let max_degree = degrees.values().cloned().max().unwrap_or(0);
+
+    // Buckets of nodes by current degree; entries go stale when a degree drops and are skipped
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); max_degree + 1];
+    let mut nodes: Vec<usize> = graph.keys().cloned().collect();
+    nodes.sort_unstable();
+    for node in nodes {
+        buckets[degrees[&node]].push(node);
+    }
+
+    let mut removed = HashSet::new();
+    let mut order = Vec::with_capacity(graph.len());
+    let mut smallest = 0;
+    while order.len() < graph.len() {
+        while buckets[smallest].is_empty() {
+            smallest += 1;
+        }
+        let node = buckets[smallest].pop().unwrap();
+        if removed.contains(&node) || degrees[&node] != smallest {
+            continue;
+        }
+        removed.insert(node);
+        order.push(node);
+        for neighbor in &graph[&node] {
+            if *neighbor != node && !removed.contains(neighbor) {
+                if let Some(degree) = degrees.get_mut(neighbor) {
+                    *degree -= 1;
+                    buckets[*degree].push(*neighbor);
+                    smallest = smallest.min(*degree);
+                }
+            }
+        }
+    }
+
+    order
+}
+
+// Recursive Bron–Kerbosch step: extend the clique `current` with candidates, never reporting a clique that could also take a node from `excluded`
+fn bron_kerbosch<F: FnMut(&[usize])>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    current: &mut Vec<usize>,
+    mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>,
+    visit: &mut F,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            visit(current);
+        }
+        return;
+    }
+
+    // Pivot on the node covering the most candidates; only candidates outside its neighborhood need their own branch
+    let pivot = candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|&&u| (graph[&u].iter().filter(|v| candidates.contains(v)).count(), std::cmp::Reverse(u)))
+        .cloned()
+        .unwrap();
+    let mut branches: Vec<usize> = candidates.iter().filter(|v| !graph[&pivot].contains(v)).cloned().collect();
+    branches.sort_unstable();
+
+    for node in branches {
+        let neighbors = &graph[&node];
+        current.push(node);
+        bron_kerbosch(
+            graph,
+            current,
+            candidates.iter().filter(|v| neighbors.contains(v)).cloned().collect(),
+            excluded.iter().filter(|v| neighbors.contains(v)).cloned().collect(),
+            visit,
+        );
+        current.pop();
+        candidates.remove(&node);
+        excluded.insert(node);
+    }
+}
+
+// Neighbors of a node without a self-loop
+fn neighbors_of(graph: &HashMap<usize, HashSet<usize>>, node: usize) -> HashSet<usize> {
+    graph.get(&node).map_or_else(HashSet::new, |neighbors| neighbors.iter().filter(|&&n| n != node).cloned().collect())
+}
+
+// FOR EACH MAXIMAL CLIQUE. Call visit with every maximal clique of the graph, without storing them all (there can be millions on the full dataset)
+pub fn for_each_maximal_clique<F: FnMut(&[usize])>(graph: &HashMap<usize, HashSet<usize>>, mut visit: F) {
+    let order = degeneracy_ordering(graph);
+    let position: HashMap<usize, usize> = order.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+    let simple: HashMap<usize, HashSet<usize>> = graph.keys().map(|&node| (node, neighbors_of(graph, node))).collect();
+
+    // Each clique is found from its earliest node in the order: later neighbors are candidates, earlier ones excluded
+    for (i, &node) in order.iter().enumerate() {
+        let (later, earlier): (HashSet<usize>, HashSet<usize>) = simple[&node]
+            .iter()
+            .filter(|n| position.contains_key(n))
+            .partition(|n| position[n] > i);
+        bron_kerbosch(&simple, &mut vec![node], later, earlier, &mut visit);
+    }
+}
+
+// MAXIMAL CLIQUES. Every maximal clique with at least min_size nodes, each sorted by node ID
+pub fn maximal_cliques(graph: &HashMap<usize, HashSet<usize>>, min_size: usize) -> Vec<Vec<usize>> {
+    let mut cliques = Vec::new();
+    for_each_maximal_clique(graph, |clique| {
+        if clique.len() >= min_size {
+            let mut clique = clique.to_vec();
+            clique.sort_unstable();
+            cliques.push(clique);
+        }
+    });
+    cliques.sort();
+    cliques
+}
+
+// CLIQUES CONTAINING NODE. Every maximal clique that includes the given node, each sorted by node ID
+pub fn cliques_containing(graph: &HashMap<usize, HashSet<usize>>, node: usize) -> Vec<Vec<usize>> {
+    let mut cliques = Vec::new();
+    if !graph.contains_key(&node) {
+        return cliques;
+    }
+    let simple: HashMap<usize, HashSet<usize>> = graph.keys().map(|&n| (n, neighbors_of(graph, n))).collect();
+    let candidates = simple[&node].iter().filter(|n| simple.contains_key(n)).cloned().collect();
+    bron_kerbosch(&simple, &mut vec![node], candidates, HashSet::new(), &mut |clique: &[usize]| {
+        let mut clique = clique.to_vec();
+        clique.sort_unstable();
+        cliques.push(clique);
+    });
+    cliques.sort();
+    cliques
+}
+
+// CLIQUE SIZE DISTRIBUTION. Number of maximal cliques of each size, and one maximum clique
+pub fn clique_size_distribution(graph: &HashMap<usize, HashSet<usize>>) -> (BTreeMap<usize, usize>, Vec<usize>) {
+    let mut distribution = BTreeMap::new();
+    let mut maximum: Vec<usize> = Vec::new();
+    for_each_maximal_clique(graph, |clique| {
+        *distribution.entry(clique.len()).or_insert(0) += 1;
+        if clique.len() > maximum.len() {
+            maximum = clique.to_vec();
+        }
+    });
+    maximum.sort_unstable();
+    (distribution, maximum)
+}
@@ -0,0 +1,666 @@
+// Community detection. The clustering coefficients in analysis.rs hint at clustered communities of shared interests (e.g. Boston sports accounts); these functions actually find them.
+// Every partitioning algorithm returns a map from node ID to community ID (numbered from 0), so the results can be compared with each other and scored with modularity.
+// Overlapping algorithms return a cover instead: a map from node ID to the (sorted) IDs of every community the node belongs to.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::cliques::maximal_cliques;
+use crate::graph::index_graph;
+
+// Weighted undirected graph on node indices, used while aggregating communities into single nodes
+struct WeightedGraph {
+    // Neighbor index and edge weight, without self-loops
+    adjacency: Vec<Vec<(usize, f64)>>,
+    // Self-loop weight of each node, counted twice like any other edge (A_ii)
+    self_loops: Vec<f64>,
+    // Weighted degree of each node, including its self-loop
+    strengths: Vec<f64>,
+    // Sum of all strengths (twice the total edge weight)
+    total_weight: f64,
+}
+
+impl WeightedGraph {
+    fn from_adjacency(adjacency: &[Vec<usize>]) -> Self {
+        let mut self_loops = vec![0.0; adjacency.len()];
+        let weighted: Vec<Vec<(usize, f64)>> = adjacency
+            .iter()
+            .enumerate()
+            .map(|(node, neighbors)| {
+                neighbors
+                    .iter()
+                    .filter(|&&neighbor| {
+                        if neighbor == node {
+                            self_loops[node] += 1.0;
+                        }
+                        neighbor != node
+                    })
+                    .map(|&neighbor| (neighbor, 1.0))
+                    .collect()
+            })
+            .collect();
+        Self::new(weighted, self_loops)
+    }
+
+    fn new(adjacency: Vec<Vec<(usize, f64)>>, self_loops: Vec<f64>) -> Self {
+        let strengths: Vec<f64> = adjacency
+            .iter()
+            .zip(&self_loops)
+            .map(|(neighbors, self_loop)| neighbors.iter().map(|&(_, w)| w).sum::<f64>() + self_loop)
+            .collect();
+        let total_weight = strengths.iter().sum();
+        WeightedGraph { adjacency, self_loops, strengths, total_weight }
+    }
+
+    fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    // Collapse each community into a single node; edges inside a community become its self-loop
+    fn aggregate(&self, communities: &[usize], count: usize) -> WeightedGraph {
+        let mut self_loops = vec![0.0; count];
+        let mut edges: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        for (node, neighbors) in self.adjacency.iter().enumerate() {
+            let community = communities[node];
+            self_loops[community] += self.self_loops[node];
+            for &(neighbor, weight) in neighbors {
+                let other = communities[neighbor];
+                if other == community {
+                    self_loops[community] += weight;
+                } else {
+                    *edges[community].entry(other).or_insert(0.0) += weight;
+                }
+            }
+        }
+        let adjacency = edges
+            .into_iter()
+            .map(|neighbors| {
+                let mut neighbors: Vec<(usize, f64)> = neighbors.into_iter().collect();
+                neighbors.sort_by_key(|&(neighbor, _)| neighbor);
+                neighbors
+            })
+            .collect();
+        WeightedGraph::new(adjacency, self_loops)
+    }
+
+    // Modularity of a partition: fraction of edge weight inside communities minus the fraction expected at random
+    fn modularity(&self, communities: &[usize], resolution: f64) -> f64 {
+        if self.total_weight == 0.0 {
+            return 0.0;
+        }
+        let count = communities.iter().max().map_or(0, |&c| c + 1);
+        let mut internal = vec![0.0; count];
+        let mut totals = vec![0.0; count];
+        for (node, neighbors) in self.adjacency.iter().enumerate() {
+            let community = communities[node];
+            totals[community] += self.strengths[node];
+            internal[community] += self.self_loops[node];
+            for &(neighbor, weight) in neighbors {
+                if communities[neighbor] == community {
+                    internal[community] += weight;
+                }
+            }
+        }
+        internal
+            .iter()
+            .zip(&totals)
+            .map(|(inside, total)| inside / self.total_weight - resolution * (total / self.total_weight).powi(2))
+            .sum()
+    }
+}
+
+// Sum the edge weight from a node to each neighboring community
+fn community_weights(graph: &WeightedGraph, node: usize, communities: &[usize]) -> HashMap<usize, f64> {
+    let mut weights = HashMap::new();
+    for &(neighbor, weight) in &graph.adjacency[node] {
+        *weights.entry(communities[neighbor]).or_insert(0.0) += weight;
+    }
+    weights
+}
+
+// Pick the community with the largest modularity gain for a node that has been taken out of its community. Staying put wins ties.
+fn best_community(
+    graph: &WeightedGraph,
+    node: usize,
+    current: usize,
+    weights: &HashMap<usize, f64>,
+    totals: &[f64],
+    resolution: f64,
+) -> usize {
+    let strength = graph.strengths[node];
+    let gain = |community: usize| {
+        weights.get(&community).copied().unwrap_or(0.0) - resolution * totals[community] * strength / graph.total_weight
+    };
+
+    let mut best = current;
+    let mut best_gain = gain(current);
+    let mut candidates: Vec<usize> = weights.keys().cloned().collect();
+    candidates.sort_unstable();
+    for community in candidates {
+        let community_gain = gain(community);
+        if community_gain > best_gain + 1e-12 {
+            best = community;
+            best_gain = community_gain;
+        }
+    }
+    best
+}
+
+// Renumber community labels as 0..count in order of first appearance
+fn renumber(communities: &mut [usize]) -> usize {
+    let mut labels = HashMap::new();
+    for community in communities.iter_mut() {
+        let next = labels.len();
+        *community = *labels.entry(*community).or_insert(next);
+    }
+    labels.len()
+}
+
+// LOUVAIN LOCAL MOVING. Repeatedly sweep the nodes in random order, moving each to its best neighboring community, until a sweep moves nothing
+fn louvain_local_moving<R: Rng>(graph: &WeightedGraph, resolution: f64, rng: &mut R) -> (Vec<usize>, bool) {
+    let mut communities: Vec<usize> = (0..graph.len()).collect();
+    let mut totals = graph.strengths.clone();
+    let mut order: Vec<usize> = (0..graph.len()).collect();
+    let mut moved_any = false;
+
+    loop {
+        order.shuffle(rng);
+        let mut moved = false;
+        for &node in &order {
+            let current = communities[node];
+            let weights = community_weights(graph, node, &communities);
+            totals[current] -= graph.strengths[node];
+            let best = best_community(graph, node, current, &weights, &totals, resolution);
+            totals[best] += graph.strengths[node];
+            if best != current {
+                communities[node] = best;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+        moved_any = true;
+    }
+
+    (communities, moved_any)
+}
+
+// LOUVAIN. Alternate local moving and aggregation until no node changes community. resolution > 1 favors smaller communities, < 1 larger ones.
+pub fn louvain<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    resolution: f64,
+    rng: &mut R,
+) -> (HashMap<usize, usize>, f64) {
+    let (nodes, adjacency) = index_graph(graph);
+    let original = WeightedGraph::from_adjacency(&adjacency);
+
+    // membership maps each original node to its node in the current aggregated graph
+    let mut membership: Vec<usize> = (0..nodes.len()).collect();
+    let mut current = WeightedGraph::from_adjacency(&adjacency);
+    loop {
+        let (mut communities, moved) = louvain_local_moving(&current, resolution, rng);
+        if !moved {
+            break;
+        }
+        let count = renumber(&mut communities);
+        for member in membership.iter_mut() {
+            *member = communities[*member];
+        }
+        current = current.aggregate(&communities, count);
+    }
+
+    renumber(&mut membership);
+    let quality = original.modularity(&membership, resolution);
+    (nodes.into_iter().zip(membership).collect(), quality)
+}
+
+// LEIDEN LOCAL MOVING. Like Louvain's sweep, but after the first pass only nodes whose neighborhood changed are revisited
+fn leiden_local_moving<R: Rng>(graph: &WeightedGraph, communities: &mut [usize], resolution: f64, rng: &mut R) {
+    let mut totals = vec![0.0; graph.len()];
+    for (node, &community) in communities.iter().enumerate() {
+        totals[community] += graph.strengths[node];
+    }
+
+    let mut order: Vec<usize> = (0..graph.len()).collect();
+    order.shuffle(rng);
+    let mut queued = vec![true; graph.len()];
+    let mut queue: VecDeque<usize> = order.into();
+
+    while let Some(node) = queue.pop_front() {
+        queued[node] = false;
+        let current = communities[node];
+        let weights = community_weights(graph, node, communities);
+        totals[current] -= graph.strengths[node];
+        let best = best_community(graph, node, current, &weights, &totals, resolution);
+        totals[best] += graph.strengths[node];
+        if best != current {
+            communities[node] = best;
+            for &(neighbor, _) in &graph.adjacency[node] {
+                if !queued[neighbor] && communities[neighbor] != best {
+                    queued[neighbor] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+}
+
+// LEIDEN REFINEMENT. Split each community into well-connected pieces by merging singleton nodes only within their community, so no community ends up internally disconnected
+fn leiden_refine<R: Rng>(graph: &WeightedGraph, communities: &[usize], resolution: f64, rng: &mut R) -> Vec<usize> {
+    let mut refined: Vec<usize> = (0..graph.len()).collect();
+    let mut refined_totals = graph.strengths.clone();
+    let mut refined_sizes = vec![1usize; graph.len()];
+
+    let count = communities.iter().max().map_or(0, |&c| c + 1);
+    let mut community_totals = vec![0.0; count];
+    for (node, &community) in communities.iter().enumerate() {
+        community_totals[community] += graph.strengths[node];
+    }
+
+    // Weight from each node (and so each refined piece) to the rest of its own community
+    let mut outside_weight: Vec<f64> = (0..graph.len())
+        .map(|node| {
+            graph.adjacency[node]
+                .iter()
+                .filter(|&&(neighbor, _)| communities[neighbor] == communities[node])
+                .map(|&(_, weight)| weight)
+                .sum()
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..graph.len()).collect();
+    order.shuffle(rng);
+    for node in order {
+        if refined_sizes[refined[node]] > 1 {
+            continue;
+        }
+        let community = communities[node];
+        let strength = graph.strengths[node];
+        let community_total = community_totals[community];
+        let well_connected = |weight: f64, total: f64| weight >= resolution * total * (community_total - total) / graph.total_weight;
+        if !well_connected(outside_weight[node], strength) {
+            continue;
+        }
+
+        // Weights to the refined pieces of the same community
+        let mut weights: HashMap<usize, f64> = HashMap::new();
+        for &(neighbor, weight) in &graph.adjacency[node] {
+            if communities[neighbor] == community {
+                *weights.entry(refined[neighbor]).or_insert(0.0) += weight;
+            }
+        }
+
+        let mut candidates: Vec<usize> = weights.keys().cloned().collect();
+        candidates.sort_unstable();
+        let own = refined[node];
+        let mut best = own;
+        let mut best_gain = 0.0;
+        for piece in candidates {
+            if piece == own || !well_connected(outside_weight[piece], refined_totals[piece]) {
+                continue;
+            }
+            let gain = weights[&piece] - resolution * refined_totals[piece] * strength / graph.total_weight;
+            if gain > best_gain {
+                best = piece;
+                best_gain = gain;
+            }
+        }
+
+        if best != own {
+            outside_weight[best] += outside_weight[own] - 2.0 * weights[&best];
+            refined_totals[best] += strength;
+            refined_totals[own] = 0.0;
+            refined_sizes[best] += 1;
+            refined_sizes[own] = 0;
+            refined[node] = best;
+        }
+    }
+
+    refined
+}
+
+// LEIDEN. Louvain with a refinement step between local moving and aggregation, which guarantees well-connected communities and usually reaches higher modularity
+pub fn leiden<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    resolution: f64,
+    rng: &mut R,
+) -> (HashMap<usize, usize>, f64) {
+    let (nodes, adjacency) = index_graph(graph);
+    let original = WeightedGraph::from_adjacency(&adjacency);
+
+    let mut membership: Vec<usize> = (0..nodes.len()).collect();
+    let mut current = WeightedGraph::from_adjacency(&adjacency);
+    let mut communities: Vec<usize> = (0..current.len()).collect();
+    loop {
+        leiden_local_moving(&current, &mut communities, resolution, rng);
+        let community_count = renumber(&mut communities);
+        if community_count == current.len() {
+            break;
+        }
+
+        // Aggregate by the refined partition, starting the next level from the unrefined one
+        let mut refined = leiden_refine(&current, &communities, resolution, rng);
+        let refined_count = renumber(&mut refined);
+        if refined_count == current.len() {
+            break;
+        }
+        let mut next_communities = vec![0; refined_count];
+        for (node, &piece) in refined.iter().enumerate() {
+            next_communities[piece] = communities[node];
+        }
+        for member in membership.iter_mut() {
+            *member = refined[*member];
+        }
+        current = current.aggregate(&refined, refined_count);
+        communities = next_communities;
+    }
+
+    for member in membership.iter_mut() {
+        *member = communities[*member];
+    }
+    renumber(&mut membership);
+    let quality = original.modularity(&membership, resolution);
+    (nodes.into_iter().zip(membership).collect(), quality)
+}
+
+// Pick one of the most frequent labels among a node's neighbors, keeping the current label if it is one of them
+fn dominant_label<R: Rng>(neighbors: &[usize], labels: &[usize], current: usize, rng: &mut R) -> usize {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for &neighbor in neighbors {
+        *counts.entry(labels[neighbor]).or_insert(0) += 1;
+    }
+    let Some(&most) = counts.values().max() else {
+        return current;
+    };
+    if counts.get(&current) == Some(&most) {
+        return current;
+    }
+    let mut best: Vec<usize> = counts.into_iter().filter(|&(_, count)| count == most).map(|(label, _)| label).collect();
+    best.sort_unstable();
+    *best.choose(rng).unwrap()
+}
+
+// LABEL PROPAGATION. Every node starts with its own label and repeatedly adopts the most common label among its neighbors until nothing changes.
+// Asynchronous updates (synchronous = false) use the newest labels and usually settle quickly; synchronous updates can oscillate. Ties between labels
+// can keep either mode flipping, so both are capped at max_iterations sweeps.
+pub fn label_propagation<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    synchronous: bool,
+    max_iterations: usize,
+    rng: &mut R,
+) -> HashMap<usize, usize> {
+    let (nodes, adjacency) = index_graph(graph);
+    let mut labels: Vec<usize> = (0..nodes.len()).collect();
+    let mut order: Vec<usize> = (0..nodes.len()).collect();
+
+    for _ in 0..max_iterations {
+        order.shuffle(rng);
+        let mut changed = false;
+        if synchronous {
+            let previous = labels.clone();
+            for &node in &order {
+                labels[node] = dominant_label(&adjacency[node], &previous, previous[node], rng);
+                changed |= labels[node] != previous[node];
+            }
+        } else {
+            for &node in &order {
+                let label = dominant_label(&adjacency[node], &labels, labels[node], rng);
+                changed |= label != labels[node];
+                labels[node] = label;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    renumber(&mut labels);
+    nodes.into_iter().zip(labels).collect()
+}
+
+// FLUID COMMUNITIES. k communities start at random nodes and expand and contract like fluids: each community has density 1 / size, and a node joins the community with the highest total density among itself and its neighbors.
+// Components that no community reaches become one extra community each.
+pub fn fluid_communities<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    k: usize,
+    max_iterations: usize,
+    rng: &mut R,
+) -> HashMap<usize, usize> {
+    let (nodes, adjacency) = index_graph(graph);
+    let mut order: Vec<usize> = (0..nodes.len()).collect();
+    let k = k.clamp(1, nodes.len().max(1));
+
+    let mut labels: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut sizes = vec![0usize; k];
+    order.shuffle(rng);
+    for (community, &node) in order.iter().take(k).enumerate() {
+        labels[node] = Some(community);
+        sizes[community] = 1;
+    }
+
+    for _ in 0..max_iterations {
+        order.shuffle(rng);
+        let mut changed = false;
+        for &node in &order {
+            let mut density: HashMap<usize, f64> = HashMap::new();
+            for &member in std::iter::once(&node).chain(&adjacency[node]) {
+                if let Some(community) = labels[member] {
+                    *density.entry(community).or_insert(0.0) += 1.0 / sizes[community] as f64;
+                }
+            }
+            let Some(most) = density.values().cloned().reduce(f64::max) else {
+                continue;
+            };
+            let current = labels[node];
+            if current.is_some_and(|c| density.get(&c).is_some_and(|&d| d >= most - 1e-12)) {
+                continue;
+            }
+
+            let mut best: Vec<usize> = density.into_iter().filter(|&(_, d)| d >= most - 1e-12).map(|(c, _)| c).collect();
+            best.sort_unstable();
+            let chosen = *best.choose(rng).unwrap();
+            if let Some(old) = current {
+                // A community never gives up its last node
+                if sizes[old] == 1 {
+                    continue;
+                }
+                sizes[old] -= 1;
+            }
+            sizes[chosen] += 1;
+            labels[node] = Some(chosen);
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Give each unreached component its own community
+    let mut next = k;
+    for start in 0..nodes.len() {
+        if labels[start].is_some() {
+            continue;
+        }
+        labels[start] = Some(next);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            for &neighbor in &adjacency[current] {
+                if labels[neighbor].is_none() {
+                    labels[neighbor] = Some(next);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        next += 1;
+    }
+
+    let mut labels: Vec<usize> = labels.into_iter().map(|label| label.unwrap()).collect();
+    renumber(&mut labels);
+    nodes.into_iter().zip(labels).collect()
+}
+
+// MODULARITY. Score any node-to-community assignment on the graph (nodes without a community count as their own singleton)
+pub fn modularity(
+    graph: &HashMap<usize, HashSet<usize>>,
+    communities: &HashMap<usize, usize>,
+    resolution: f64,
+) -> f64 {
+    let (nodes, adjacency) = index_graph(graph);
+    let mut assignment: Vec<usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| communities.get(node).map_or(nodes.len() + i, |&c| c))
+        .collect();
+    renumber(&mut assignment);
+    WeightedGraph::from_adjacency(&adjacency).modularity(&assignment, resolution)
+}
+
+// LARGEST COMMUNITIES. The biggest communities by size, each with its members sorted by degree (highest first)
+pub fn largest_communities(
+    graph: &HashMap<usize, HashSet<usize>>,
+    communities: &HashMap<usize, usize>,
+    count: usize,
+) -> Vec<(usize, Vec<usize>)> {
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (&node, &community) in communities {
+        members.entry(community).or_default().push(node);
+    }
+
+    let degree = |node: &usize| graph.get(node).map_or(0, |n| n.len());
+    let mut largest: Vec<(usize, Vec<usize>)> = members.into_iter().collect();
+    for (_, nodes) in largest.iter_mut() {
+        nodes.sort_by(|a, b| degree(b).cmp(&degree(a)).then(a.cmp(b)));
+    }
+    largest.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+    largest.truncate(count);
+
+    largest
+}
+
+// Find the root of a union-find set, compressing the path on the way
+fn find_root(parents: &mut [usize], mut item: usize) -> usize {
+    while parents[item] != item {
+        parents[item] = parents[parents[item]];
+        item = parents[item];
+    }
+    item
+}
+
+// Build a cover from a list of communities, numbering them 0.. in the given order
+fn communities_to_cover(communities: &[HashSet<usize>]) -> HashMap<usize, Vec<usize>> {
+    let mut cover: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (id, members) in communities.iter().enumerate() {
+        for &node in members {
+            cover.entry(node).or_default().push(id);
+        }
+    }
+    cover
+}
+
+// CLIQUE PERCOLATION. k-clique communities: unions of k-cliques that can be reached from one another through k-cliques sharing k - 1 nodes.
+// Nodes in no k-clique belong to no community, and a node can belong to several.
+pub fn clique_percolation(graph: &HashMap<usize, HashSet<usize>>, k: usize) -> HashMap<usize, Vec<usize>> {
+    let k = k.max(2);
+    // Two k-cliques are adjacent exactly when the maximal cliques containing them share at least k - 1 nodes
+    let cliques = maximal_cliques(graph, k);
+    let mut containing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (id, clique) in cliques.iter().enumerate() {
+        for &node in clique {
+            containing.entry(node).or_default().push(id);
+        }
+    }
+
+    let mut parents: Vec<usize> = (0..cliques.len()).collect();
+    for (id, clique) in cliques.iter().enumerate() {
+        let mut shared: HashMap<usize, usize> = HashMap::new();
+        for node in clique {
+            for &other in &containing[node] {
+                if other > id {
+                    *shared.entry(other).or_insert(0) += 1;
+                }
+            }
+        }
+        for (other, count) in shared {
+            if count + 1 >= k {
+                let (a, b) = (find_root(&mut parents, id), find_root(&mut parents, other));
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    // Merge the cliques of each connected group into one community, numbered by first clique
+    let mut communities: Vec<HashSet<usize>> = Vec::new();
+    let mut community_of_root: HashMap<usize, usize> = HashMap::new();
+    for (id, clique) in cliques.iter().enumerate() {
+        let root = find_root(&mut parents, id);
+        let next = communities.len();
+        let community = *community_of_root.entry(root).or_insert(next);
+        if community == next {
+            communities.push(HashSet::new());
+        }
+        communities[community].extend(clique.iter().cloned());
+    }
+
+    communities_to_cover(&communities)
+}
+
+// EGO-SPLITTING. Split every node into one persona per connected component of its ego network (its neighbors, without itself), connect personas
+// through the original edges, partition the persona graph with Louvain, and give each node the communities of all its personas
+pub fn ego_splitting<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    resolution: f64,
+    rng: &mut R,
+) -> HashMap<usize, Vec<usize>> {
+    let mut nodes: Vec<usize> = graph.keys().cloned().collect();
+    nodes.sort_unstable();
+
+    // persona[node][neighbor] is the persona of node that the edge to neighbor attaches to
+    let mut persona: HashMap<usize, HashMap<usize, usize>> = HashMap::new();
+    let mut owner: Vec<usize> = Vec::new();
+    for &node in &nodes {
+        let ego: HashSet<usize> = graph[&node].iter().filter(|&&n| n != node).cloned().collect();
+        let mut assigned: HashMap<usize, usize> = HashMap::new();
+        let mut sorted_ego: Vec<usize> = ego.iter().cloned().collect();
+        sorted_ego.sort_unstable();
+        for start in sorted_ego {
+            if assigned.contains_key(&start) {
+                continue;
+            }
+            let id = owner.len();
+            owner.push(node);
+            assigned.insert(start, id);
+            let mut queue = VecDeque::from([start]);
+            while let Some(current) = queue.pop_front() {
+                for neighbor in graph.get(&current).into_iter().flatten() {
+                    if ego.contains(neighbor) && !assigned.contains_key(neighbor) {
+                        assigned.insert(*neighbor, id);
+                        queue.push_back(*neighbor);
+                    }
+                }
+            }
+        }
+        persona.insert(node, assigned);
+    }
+
+    let mut persona_graph: HashMap<usize, HashSet<usize>> = HashMap::new();
+    for &node in &nodes {
+        for (neighbor, &from) in &persona[&node] {
+            if let Some(&to) = persona.get(neighbor).and_then(|p| p.get(&node)) {
+                persona_graph.entry(from).or_default().insert(to);
+                persona_graph.entry(to).or_default().insert(from);
+            }
+        }
+    }
+
+    let (persona_communities, _) = louvain(&persona_graph, resolution, rng);
+    let mut cover: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (persona_id, community) in persona_communities {
+        cover.entry(owner[persona_id]).or_default().push(community);
+    }
+    for communities in cover.values_mut() {
+        communities.sort_unstable();
+        communities.dedup();
+    }
+    cover
+}
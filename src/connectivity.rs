@@ -0,0 +1,126 @@
+// Weak points of an undirected graph: articulation points (accounts whose removal disconnects part of the network), bridges (links whose
+// removal does), and biconnected components (maximal groups that stay connected after losing any one account). All three come from a single
+// Hopcroft–Tarjan depth-first search, run with an explicit stack so that the long paths of the full graph cannot overflow the call stack.
+
+use std::collections::{HashMap, HashSet};
+use crate::graph::index_graph;
+
+// Result of the depth-first search, on indices from index_graph
+struct Decomposition {
+    nodes: Vec<usize>,
+    articulation: Vec<bool>,
+    bridges: Vec<(usize, usize)>,
+    components: Vec<Vec<usize>>,
+}
+
+fn decompose(graph: &HashMap<usize, HashSet<usize>>) -> Decomposition {
+    let (nodes, adjacency) = index_graph(graph);
+    let n = nodes.len();
+    const UNVISITED: usize = usize::MAX;
+    let mut discovery = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut articulation = vec![false; n];
+    let mut bridges = Vec::new();
+    let mut components = Vec::new();
+
+    // DFS frames are (node, parent, index of the next neighbor to look at); edges wait on edge_stack until their component is complete
+    let mut stack: Vec<(usize, usize, usize)> = Vec::new();
+    let mut edge_stack: Vec<(usize, usize)> = Vec::new();
+    let mut time = 0;
+
+    for root in 0..n {
+        if discovery[root] != UNVISITED {
+            continue;
+        }
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        stack.push((root, UNVISITED, 0));
+
+        while let Some(frame) = stack.last_mut() {
+            let (node, parent) = (frame.0, frame.1);
+            if frame.2 < adjacency[node].len() {
+                let neighbor = adjacency[node][frame.2];
+                frame.2 += 1;
+                if neighbor == parent || neighbor == node {
+                    continue;
+                }
+                if discovery[neighbor] == UNVISITED {
+                    // Tree edge
+                    edge_stack.push((node, neighbor));
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    time += 1;
+                    if node == root {
+                        root_children += 1;
+                    }
+                    stack.push((neighbor, node, 0));
+                } else if discovery[neighbor] < discovery[node] {
+                    // Back edge to an ancestor
+                    edge_stack.push((node, neighbor));
+                    low[node] = low[node].min(discovery[neighbor]);
+                }
+                continue;
+            }
+
+            // All neighbors done: report to the parent
+            stack.pop();
+            if parent == UNVISITED {
+                continue;
+            }
+            low[parent] = low[parent].min(low[node]);
+            if low[node] > discovery[parent] {
+                bridges.push((nodes[parent].min(nodes[node]), nodes[parent].max(nodes[node])));
+            }
+            if low[node] >= discovery[parent] {
+                // Nothing below node reaches above parent, so parent separates this subtree and its edges form a component
+                if parent != root {
+                    articulation[parent] = true;
+                }
+                let mut component = HashSet::new();
+                while let Some((a, b)) = edge_stack.pop() {
+                    component.insert(nodes[a]);
+                    component.insert(nodes[b]);
+                    if (a, b) == (parent, node) {
+                        break;
+                    }
+                }
+                let mut component: Vec<usize> = component.into_iter().collect();
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+
+        if root_children > 1 {
+            articulation[root] = true;
+        }
+    }
+
+    bridges.sort_unstable();
+    components.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    Decomposition { nodes, articulation, bridges, components }
+}
+
+// ARTICULATION POINTS. Nodes whose removal increases the number of connected components, in ascending order
+pub fn articulation_points(graph: &HashMap<usize, HashSet<usize>>) -> Vec<usize> {
+    let decomposition = decompose(graph);
+    decomposition
+        .nodes
+        .into_iter()
+        .zip(decomposition.articulation)
+        .filter(|&(_, is_articulation)| is_articulation)
+        .map(|(node, _)| node)
+        .collect()
+}
+
+// BRIDGES. Edges whose removal increases the number of connected components, as (smaller ID, larger ID) in ascending order
+pub fn bridges(graph: &HashMap<usize, HashSet<usize>>) -> Vec<(usize, usize)> {
+    decompose(graph).bridges
+}
+
+// BICONNECTED COMPONENTS. Node sets of the maximal biconnected subgraphs, largest first (a bridge is a two-node component; isolated nodes are
+// in none). Articulation points are exactly the nodes in more than one component.
+pub fn biconnected_components(graph: &HashMap<usize, HashSet<usize>>) -> Vec<Vec<usize>> {
+    decompose(graph).components
+}
@@ -0,0 +1,379 @@
+// Degree distribution analysis. degree_centrality in graph.rs gives each account's degree; these functions describe the distribution of those degrees:
+// the histogram, the complementary CDF, and a maximum-likelihood power-law fit following Clauset, Shalizi and Newman (2009), with likelihood-ratio
+// comparisons against log-normal and exponential tails. All fits treat degrees as discrete.
+// Degree correlations ask who connects to whom: the assortativity coefficient, the average neighbor degree k_nn(k), and the rich-club coefficient.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::f64::consts::SQRT_2;
+use rand::Rng;
+use crate::nullmodel::randomized_copy;
+
+// Power-law fit of a degree tail: P(k) proportional to k^-alpha for k >= xmin
+pub struct PowerLawFit {
+    pub alpha: f64,
+    pub xmin: usize,
+    // Kolmogorov–Smirnov distance between the tail and the fitted distribution
+    pub ks_statistic: f64,
+    // Number of degrees >= xmin
+    pub tail_size: usize,
+}
+
+// Tails with fewer values than this are too small to fit
+const MIN_TAIL: usize = 10;
+
+// DEGREE HISTOGRAM. Number of nodes with each degree
+pub fn degree_histogram(degrees: &[usize]) -> BTreeMap<usize, usize> {
+    let mut histogram = BTreeMap::new();
+    for &degree in degrees {
+        *histogram.entry(degree).or_insert(0) += 1;
+    }
+    histogram
+}
+
+// DEGREE CCDF. For each degree k that occurs, the fraction of nodes with degree >= k
+pub fn degree_ccdf(degrees: &[usize]) -> Vec<(usize, f64)> {
+    let total = degrees.len() as f64;
+    let mut remaining = degrees.len();
+    let mut ccdf = Vec::new();
+    for (degree, count) in degree_histogram(degrees) {
+        ccdf.push((degree, remaining as f64 / total));
+        remaining -= count;
+    }
+    ccdf
+}
+
+// Hurwitz zeta function sum over k >= 0 of (q + k)^-s, for s > 1 and q > 0, by Euler–Maclaurin summation
+fn hurwitz_zeta(s: f64, q: f64) -> f64 {
+    const TERMS: usize = 10;
+    // Bernoulli numbers B2, B4, ..., B12 divided by their factorial indices (2j)!
+    const CORRECTIONS: [f64; 6] = [
+        1.0 / 12.0,
+        -1.0 / 720.0,
+        1.0 / 30240.0,
+        -1.0 / 1209600.0,
+        1.0 / 47900160.0,
+        -691.0 / 1307674368000.0,
+    ];
+
+    let mut sum: f64 = (0..TERMS).map(|k| (q + k as f64).powf(-s)).sum();
+    let tail = q + TERMS as f64;
+    sum += tail.powf(1.0 - s) / (s - 1.0) + 0.5 * tail.powf(-s);
+
+    // Each correction term carries the rising factorial s (s+1) ... (s+2j-2)
+    let mut rising = s;
+    let mut power = tail.powf(-s - 1.0);
+    for (j, correction) in CORRECTIONS.iter().enumerate() {
+        sum += correction * rising * power;
+        let next = 2.0 * j as f64 + s;
+        rising *= (next + 1.0) * (next + 2.0);
+        power /= tail * tail;
+    }
+    sum
+}
+
+// Maximize a one-dimensional function on [low, high] by golden-section search
+fn golden_section_max<F: Fn(f64) -> f64>(f: F, mut low: f64, mut high: f64) -> f64 {
+    let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let mut a = high - ratio * (high - low);
+    let mut b = low + ratio * (high - low);
+    let (mut fa, mut fb) = (f(a), f(b));
+    while high - low > 1e-7 {
+        if fa < fb {
+            low = a;
+            a = b;
+            fa = fb;
+            b = low + ratio * (high - low);
+            fb = f(b);
+        } else {
+            high = b;
+            b = a;
+            fb = fa;
+            a = high - ratio * (high - low);
+            fa = f(a);
+        }
+    }
+    (low + high) / 2.0
+}
+
+// Discrete power-law MLE of alpha for a tail starting at xmin
+fn power_law_alpha(tail: &[usize], xmin: usize) -> f64 {
+    let n = tail.len() as f64;
+    let log_sum: f64 = tail.iter().map(|&x| (x as f64).ln()).sum();
+    let log_likelihood = |alpha: f64| -n * hurwitz_zeta(alpha, xmin as f64).ln() - alpha * log_sum;
+    golden_section_max(log_likelihood, 1.0001, 6.0)
+}
+
+// FIT POWER LAW. Choose xmin to minimize the KS distance between the tail and its fitted power law, as in Clauset, Shalizi and Newman.
+// Degrees of 0 are ignored. Returns None if fewer than 10 positive degrees are given.
+pub fn fit_power_law(degrees: &[usize]) -> Option<PowerLawFit> {
+    let mut sorted: Vec<usize> = degrees.iter().cloned().filter(|&d| d > 0).collect();
+    sorted.sort_unstable();
+    if sorted.len() < MIN_TAIL {
+        return None;
+    }
+
+    let mut candidates: Vec<usize> = sorted.clone();
+    candidates.dedup();
+
+    let mut best: Option<PowerLawFit> = None;
+    for xmin in candidates {
+        let start = sorted.partition_point(|&d| d < xmin);
+        let tail = &sorted[start..];
+        if tail.len() < MIN_TAIL {
+            break;
+        }
+        let alpha = power_law_alpha(tail, xmin);
+
+        // Largest gap between the empirical and fitted CDFs over the values in the tail
+        let normalization = hurwitz_zeta(alpha, xmin as f64);
+        let n = tail.len() as f64;
+        let mut ks_statistic: f64 = 0.0;
+        let mut index = 0;
+        while index < tail.len() {
+            let value = tail[index];
+            let below = index as f64 / n;
+            while index < tail.len() && tail[index] == value {
+                index += 1;
+            }
+            let empirical = index as f64 / n;
+            let model = 1.0 - hurwitz_zeta(alpha, value as f64 + 1.0) / normalization;
+            let model_below = 1.0 - hurwitz_zeta(alpha, value as f64) / normalization;
+            ks_statistic = ks_statistic.max((empirical - model).abs()).max((below - model_below).abs());
+        }
+
+        if best.as_ref().is_none_or(|fit| ks_statistic < fit.ks_statistic) {
+            best = Some(PowerLawFit { alpha, xmin, ks_statistic, tail_size: tail.len() });
+        }
+    }
+
+    best
+}
+
+// Complementary error function (Numerical Recipes' Chebyshev fit, fractional error below 1.2e-7 everywhere)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let result = t * (-z * z - 1.26551223
+        + t * (1.00002368
+        + t * (0.37409196
+        + t * (0.09678418
+        + t * (-0.18628806
+        + t * (0.27886807
+        + t * (-1.13520398
+        + t * (1.48851587
+        + t * (-0.82215223
+        + t * 0.17087277)))))))))
+        .exp();
+    if x >= 0.0 { result } else { 2.0 - result }
+}
+
+// Probability that a standard normal variable exceeds z
+fn normal_upper_tail(z: f64) -> f64 {
+    0.5 * erfc(z / SQRT_2)
+}
+
+// Log-probability of each tail value under a discrete log-normal (continuous log-normal mass in [x - 0.5, x + 0.5), renormalized to x >= xmin)
+fn log_normal_log_probabilities(tail: &[usize], xmin: usize, mu: f64, sigma: f64) -> Vec<f64> {
+    let upper = |x: f64| normal_upper_tail((x.ln() - mu) / sigma);
+    let normalization = upper(xmin as f64 - 0.5);
+    tail.iter()
+        .map(|&x| {
+            let mass = upper(x as f64 - 0.5) - upper(x as f64 + 0.5);
+            (mass.max(f64::MIN_POSITIVE) / normalization.max(f64::MIN_POSITIVE)).ln()
+        })
+        .collect()
+}
+
+// Minimize a two-parameter function with the Nelder–Mead simplex method
+fn nelder_mead<F: Fn(f64, f64) -> f64>(f: F, start: (f64, f64), step: f64) -> (f64, f64) {
+    let mut simplex = [start, (start.0 + step, start.1), (start.0, start.1 + step)];
+    let mut values = simplex.map(|(a, b)| f(a, b));
+    for _ in 0..500 {
+        // Order best to worst
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+        simplex = order.map(|i| simplex[i]);
+        values = order.map(|i| values[i]);
+        if (values[2] - values[0]).abs() < 1e-10 {
+            break;
+        }
+
+        let centroid = ((simplex[0].0 + simplex[1].0) / 2.0, (simplex[0].1 + simplex[1].1) / 2.0);
+        let towards = |scale: f64| (centroid.0 + scale * (simplex[2].0 - centroid.0), centroid.1 + scale * (simplex[2].1 - centroid.1));
+        let reflected = towards(-1.0);
+        let reflected_value = f(reflected.0, reflected.1);
+        if reflected_value < values[0] {
+            let expanded = towards(-2.0);
+            let expanded_value = f(expanded.0, expanded.1);
+            (simplex[2], values[2]) = if expanded_value < reflected_value { (expanded, expanded_value) } else { (reflected, reflected_value) };
+        } else if reflected_value < values[1] {
+            (simplex[2], values[2]) = (reflected, reflected_value);
+        } else {
+            let contracted = towards(0.5);
+            let contracted_value = f(contracted.0, contracted.1);
+            if contracted_value < values[2] {
+                (simplex[2], values[2]) = (contracted, contracted_value);
+            } else {
+                // Shrink towards the best point
+                for i in 1..3 {
+                    simplex[i] = ((simplex[0].0 + simplex[i].0) / 2.0, (simplex[0].1 + simplex[i].1) / 2.0);
+                    values[i] = f(simplex[i].0, simplex[i].1);
+                }
+            }
+        }
+    }
+    simplex[0]
+}
+
+// LIKELIHOOD RATIO TESTS. Compare the power-law fit with log-normal and exponential fits of the same tail using Vuong's test.
+// Returns (alternative, log-likelihood ratio R, p-value): R > 0 favors the power law, R < 0 the alternative, and only p < 0.1 makes the sign meaningful.
+pub fn likelihood_ratio_tests(degrees: &[usize], fit: &PowerLawFit) -> Vec<(&'static str, f64, f64)> {
+    let tail: Vec<usize> = degrees.iter().cloned().filter(|&d| d >= fit.xmin && d > 0).collect();
+    let n = tail.len() as f64;
+    let xmin = fit.xmin as f64;
+
+    let zeta = hurwitz_zeta(fit.alpha, xmin);
+    let power_law: Vec<f64> = tail.iter().map(|&x| -fit.alpha * (x as f64).ln() - zeta.ln()).collect();
+
+    // Discrete exponential (geometric) tail: P(x) = (1 - e^-lambda) e^-lambda (x - xmin)
+    let mean = tail.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let lambda = (1.0 + 1.0 / (mean - xmin).max(1e-9)).ln();
+    let exponential: Vec<f64> = tail.iter().map(|&x| (1.0 - (-lambda).exp()).ln() - lambda * (x as f64 - xmin)).collect();
+
+    // Log-normal parameters by numerical maximum likelihood, starting from the moments of log degree
+    let logs: Vec<f64> = tail.iter().map(|&x| (x as f64).ln()).collect();
+    let log_mean = logs.iter().sum::<f64>() / n;
+    let log_sd = (logs.iter().map(|l| (l - log_mean).powi(2)).sum::<f64>() / n).sqrt().max(0.1);
+    let negative_log_likelihood = |mu: f64, log_sigma: f64| -> f64 {
+        -log_normal_log_probabilities(&tail, fit.xmin, mu, log_sigma.exp()).iter().sum::<f64>()
+    };
+    let (mu, log_sigma) = nelder_mead(negative_log_likelihood, (log_mean, log_sd.ln()), 0.5);
+    let log_normal = log_normal_log_probabilities(&tail, fit.xmin, mu, log_sigma.exp());
+
+    let vuong = |alternative: &[f64]| -> (f64, f64) {
+        let differences: Vec<f64> = power_law.iter().zip(alternative).map(|(p, a)| p - a).collect();
+        let ratio: f64 = differences.iter().sum();
+        let mean_difference = ratio / n;
+        let variance = differences.iter().map(|d| (d - mean_difference).powi(2)).sum::<f64>() / n;
+        let p_value = if variance > 0.0 { erfc(ratio.abs() / (2.0 * n * variance).sqrt()) } else { 1.0 };
+        (ratio, p_value)
+    };
+
+    let (log_normal_ratio, log_normal_p) = vuong(&log_normal);
+    let (exponential_ratio, exponential_p) = vuong(&exponential);
+    vec![
+        ("log-normal", log_normal_ratio, log_normal_p),
+        ("exponential", exponential_ratio, exponential_p),
+    ]
+}
+
+// DEGREE ASSORTATIVITY. Pearson correlation between source_degrees[u] and target_degrees[v] over every edge u -> v.
+// On an undirected graph (each edge stored both ways) passing degree_centrality twice gives Newman's r; on a directed graph the four combinations
+// of in- and out-degrees give the four directed coefficients. Positive values mean high-degree nodes link to other high-degree nodes.
+pub fn degree_assortativity(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source_degrees: &HashMap<usize, usize>,
+    target_degrees: &HashMap<usize, usize>,
+) -> f64 {
+    let degree = |degrees: &HashMap<usize, usize>, node: &usize| degrees.get(node).copied().unwrap_or(0) as f64;
+    let (mut n, mut sum_x, mut sum_y, mut sum_xx, mut sum_yy, mut sum_xy) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    for (source, neighbors) in graph {
+        let x = degree(source_degrees, source);
+        for target in neighbors {
+            let y = degree(target_degrees, target);
+            n += 1.0;
+            sum_x += x;
+            sum_y += y;
+            sum_xx += x * x;
+            sum_yy += y * y;
+            sum_xy += x * y;
+        }
+    }
+    if n == 0.0 {
+        return 0.0;
+    }
+
+    let covariance = sum_xy / n - (sum_x / n) * (sum_y / n);
+    let variance_x = sum_xx / n - (sum_x / n).powi(2);
+    let variance_y = sum_yy / n - (sum_y / n).powi(2);
+    if variance_x <= 0.0 || variance_y <= 0.0 {
+        return 0.0;
+    }
+    covariance / (variance_x * variance_y).sqrt()
+}
+
+// AVERAGE NEIGHBOR DEGREE. k_nn(k): over all nodes with source degree k, the mean target degree of the nodes they link to.
+// Rising k_nn(k) is the same assortative mixing that degree_assortativity summarizes in one number; falling k_nn(k) is disassortative.
+pub fn average_neighbor_degree(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source_degrees: &HashMap<usize, usize>,
+    target_degrees: &HashMap<usize, usize>,
+) -> BTreeMap<usize, f64> {
+    // Summed neighbor degree and number of edges for each source degree
+    let mut totals: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+    for (source, neighbors) in graph {
+        if neighbors.is_empty() {
+            continue;
+        }
+        let k = source_degrees.get(source).copied().unwrap_or(0);
+        let entry = totals.entry(k).or_insert((0, 0));
+        entry.0 += neighbors.iter().map(|n| target_degrees.get(n).copied().unwrap_or(0)).sum::<usize>();
+        entry.1 += neighbors.len();
+    }
+
+    totals.into_iter().map(|(k, (sum, count))| (k, sum as f64 / count as f64)).collect()
+}
+
+// RICH-CLUB COEFFICIENT. For each degree k, the density of the subgraph of nodes with degree greater than k (undirected graph).
+// Only degrees leaving at least two such nodes are reported.
+pub fn rich_club_coefficient(graph: &HashMap<usize, HashSet<usize>>) -> BTreeMap<usize, f64> {
+    let mut degrees: Vec<usize> = graph.values().map(HashSet::len).collect();
+    degrees.sort_unstable();
+    // An edge stays in the rich club of k as long as both endpoints have degree above k, i.e. while k is below the smaller endpoint degree
+    let mut edge_degrees: Vec<usize> = graph
+        .iter()
+        .flat_map(|(&a, neighbors)| neighbors.iter().filter(move |&&b| a < b).map(move |b| (a, b)))
+        .map(|(a, b)| graph[&a].len().min(graph.get(b).map_or(0, HashSet::len)))
+        .collect();
+    edge_degrees.sort_unstable();
+
+    let max_degree = degrees.last().copied().unwrap_or(0);
+    let mut coefficients = BTreeMap::new();
+    for k in 0..max_degree {
+        let rich_nodes = (degrees.len() - degrees.partition_point(|&d| d <= k)) as f64;
+        if rich_nodes < 2.0 {
+            break;
+        }
+        let rich_edges = (edge_degrees.len() - edge_degrees.partition_point(|&d| d <= k)) as f64;
+        coefficients.insert(k, 2.0 * rich_edges / (rich_nodes * (rich_nodes - 1.0)));
+    }
+
+    coefficients
+}
+
+// NORMALIZED RICH CLUB. The rich-club coefficient divided by its average over `randomizations` degree-preserving randomizations of the graph
+// (swaps_per_edge double edge swaps per edge each). Returns k -> (coefficient, ratio); a ratio above 1 is a rich club that degrees alone don't explain.
+pub fn normalized_rich_club<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    randomizations: usize,
+    swaps_per_edge: usize,
+    rng: &mut R,
+) -> BTreeMap<usize, (f64, f64)> {
+    let coefficients = rich_club_coefficient(graph);
+
+    let mut random_totals: BTreeMap<usize, f64> = BTreeMap::new();
+    for _ in 0..randomizations.max(1) {
+        let randomized = randomized_copy(graph, false, swaps_per_edge, rng);
+        for (k, coefficient) in rich_club_coefficient(&randomized) {
+            *random_totals.entry(k).or_insert(0.0) += coefficient;
+        }
+    }
+
+    coefficients
+        .into_iter()
+        .filter_map(|(k, coefficient)| {
+            let random = random_totals.get(&k).copied().unwrap_or(0.0) / randomizations.max(1) as f64;
+            (random > 0.0).then_some((k, (coefficient, coefficient / random)))
+        })
+        .collect()
+}
@@ -0,0 +1,169 @@
+// Information diffusion over the follow graph. A tweet posted by an account is seen by its followers, so influence flows against the direction of
+// follows: the graphs from read_directed_graph are reversed into "who sees whose tweets". Two standard models decide who passes a tweet on:
+// - independent cascade: each newly active account gets one chance to activate each follower, with some probability per edge
+// - linear threshold: an account activates once the accounts it follows that are already active carry enough weight (1 / number followed each)
+// Spread is estimated by Monte Carlo simulation, with runs split across threads and each run seeded separately so results do not depend on
+// the number of threads.
+
+use std::collections::{HashMap, HashSet};
+use std::thread;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::graph::{index_graph, reverse_graph};
+
+// Activation probability of each edge for the independent cascade
+#[derive(Clone, Copy)]
+pub enum EdgeProbability {
+    // The same probability on every edge
+    Uniform(f64),
+    // 1 / (number of accounts the follower follows), the "weighted cascade"
+    WeightedCascade,
+}
+
+// Activation thresholds for the linear threshold model
+#[derive(Clone, Copy)]
+pub enum Thresholds {
+    // Drawn uniformly from [0, 1] for every account in every run, as in Kempe, Kleinberg and Tardos
+    Random,
+    // The same threshold for every account
+    Fixed(f64),
+}
+
+#[derive(Clone, Copy)]
+pub enum DiffusionModel {
+    IndependentCascade(EdgeProbability),
+    LinearThreshold(Thresholds),
+}
+
+// Monte Carlo estimate of a seed set's spread
+pub struct SpreadEstimate {
+    pub mean: f64,
+    pub std_dev: f64,
+    // Mean number of active accounts after each step (step 0 is the seeds alone); runs that stopped early keep their final size
+    pub curve: Vec<f64>,
+}
+
+// The follow graph indexed for simulation: followers[i] are the accounts that see account i's tweets, following[i] the accounts whose tweets i sees
+pub struct DiffusionGraph {
+    pub nodes: Vec<usize>,
+    pub followers: Vec<Vec<usize>>,
+    pub following: Vec<Vec<usize>>,
+}
+
+impl DiffusionGraph {
+    // Build from a follow graph (each follower maps to the accounts they follow)
+    pub fn new(graph: &HashMap<usize, HashSet<usize>>) -> Self {
+        let (nodes, followers) = index_graph(&reverse_graph(graph));
+        let mut following = vec![Vec::new(); nodes.len()];
+        for (node, list) in followers.iter().enumerate() {
+            for &follower in list {
+                following[follower].push(node);
+            }
+        }
+        DiffusionGraph { nodes, followers, following }
+    }
+
+    // Index of a node ID, if it is in the graph
+    pub fn index_of(&self, node: usize) -> Option<usize> {
+        self.nodes.binary_search(&node).ok()
+    }
+
+    // Probability that an active account activates the given follower under the independent cascade
+    pub fn edge_probability(&self, probability: EdgeProbability, follower: usize) -> f64 {
+        match probability {
+            EdgeProbability::Uniform(p) => p,
+            EdgeProbability::WeightedCascade => 1.0 / self.following[follower].len().max(1) as f64,
+        }
+    }
+
+    // SIMULATE. One run of the model from the given seed indices; returns the number of active accounts after each step
+    pub fn simulate<R: Rng>(&self, model: DiffusionModel, seeds: &[usize], rng: &mut R) -> Vec<usize> {
+        let mut active = vec![false; self.nodes.len()];
+        let mut frontier: Vec<usize> = Vec::new();
+        for &seed in seeds {
+            if !active[seed] {
+                active[seed] = true;
+                frontier.push(seed);
+            }
+        }
+        let mut total = frontier.len();
+        let mut curve = vec![total];
+
+        // Linear threshold state: accumulated influence and (lazily drawn) thresholds
+        let mut influence: HashMap<usize, f64> = HashMap::new();
+        let mut thresholds: HashMap<usize, f64> = HashMap::new();
+
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for &node in &frontier {
+                for &follower in &self.followers[node] {
+                    if active[follower] {
+                        continue;
+                    }
+                    let activated = match model {
+                        DiffusionModel::IndependentCascade(probability) => rng.gen::<f64>() < self.edge_probability(probability, follower),
+                        DiffusionModel::LinearThreshold(kind) => {
+                            let threshold = *thresholds.entry(follower).or_insert_with(|| match kind {
+                                Thresholds::Random => rng.gen::<f64>(),
+                                Thresholds::Fixed(value) => value,
+                            });
+                            let weight = influence.entry(follower).or_insert(0.0);
+                            *weight += 1.0 / self.following[follower].len().max(1) as f64;
+                            // A small tolerance lets a threshold of 1 be reached by summing 1/k k times
+                            *weight >= threshold - 1e-12
+                        }
+                    };
+                    if activated {
+                        active[follower] = true;
+                        next.push(follower);
+                    }
+                }
+            }
+            total += next.len();
+            if !next.is_empty() {
+                curve.push(total);
+            }
+            frontier = next;
+        }
+
+        curve
+    }
+
+    // EXPECTED SPREAD. Average over `runs` simulations, spread across all available threads; run r uses an rng seeded from (seed, r)
+    pub fn expected_spread(&self, model: DiffusionModel, seeds: &[usize], runs: usize, seed: u64) -> SpreadEstimate {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get()).min(runs.max(1));
+        let curves: Vec<Vec<usize>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|thread_index| {
+                    scope.spawn(move || {
+                        (thread_index..runs)
+                            .step_by(threads)
+                            .map(|run| {
+                                let mut rng = StdRng::seed_from_u64(run_seed(seed, run));
+                                self.simulate(model, seeds, &mut rng)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        });
+
+        let runs = curves.len().max(1) as f64;
+        let sizes: Vec<f64> = curves.iter().map(|curve| *curve.last().unwrap() as f64).collect();
+        let mean = sizes.iter().sum::<f64>() / runs;
+        let std_dev = (sizes.iter().map(|size| (size - mean).powi(2)).sum::<f64>() / runs).sqrt();
+
+        let steps = curves.iter().map(Vec::len).max().unwrap_or(0);
+        let curve = (0..steps)
+            .map(|step| curves.iter().map(|curve| curve[step.min(curve.len() - 1)] as f64).sum::<f64>() / runs)
+            .collect();
+
+        SpreadEstimate { mean, std_dev, curve }
+    }
+}
+
+// Independent, reproducible seed for one Monte Carlo run (or one random sample)
+pub fn run_seed(seed: u64, run: usize) -> u64 {
+    seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(run as u64)
+}
@@ -0,0 +1,113 @@
+// Statistics that only make sense when the direction of a follow is kept (graphs from read_directed_graph): how often follows are returned,
+// and how follows are arranged among every triple of accounts.
+
+use std::collections::{HashMap, HashSet};
+use crate::graph::{index_graph, reverse_graph};
+
+// The 16 isomorphism classes of directed triads in the standard MAN notation (Mutual, Asymmetric and Null dyad counts, then a type letter)
+pub const TRIAD_NAMES: [&str; 16] = [
+    "003", "012", "102", "021D", "021U", "021C", "111D", "111U", "030T", "030C", "201", "120D", "120U", "120C", "210", "300",
+];
+
+// Triad class (1-based index into TRIAD_NAMES) for each of the 64 six-bit codes built by tricode
+const TRICODES: [usize; 64] = [
+    1, 2, 2, 3, 2, 4, 6, 8, 2, 6, 5, 7, 3, 8, 7, 11, 2, 6, 4, 8, 5, 9, 9, 13, 6, 10, 9, 14, 7, 14, 12, 15, 2, 5, 6, 7, 6, 9, 10, 14, 4, 9,
+    9, 12, 8, 13, 14, 15, 3, 7, 8, 11, 7, 12, 14, 15, 8, 14, 13, 15, 11, 15, 15, 16,
+];
+
+// RECIPROCITY. Fraction of follows (excluding self-follows) that are returned
+pub fn reciprocity(graph: &HashMap<usize, HashSet<usize>>) -> f64 {
+    let mut follows = 0;
+    let mut mutual = 0;
+    for (node, followed) in graph {
+        for other in followed.iter().filter(|&other| other != node) {
+            follows += 1;
+            if graph.get(other).is_some_and(|back| back.contains(node)) {
+                mutual += 1;
+            }
+        }
+    }
+
+    if follows == 0 {
+        0.0
+    } else {
+        mutual as f64 / follows as f64
+    }
+}
+
+// NODE RECIPROCITY. For each account with at least one follow in either direction, the share of its in- and out-follows that are part of a
+// mutual pair: 2 * mutual / (in-degree + out-degree)
+pub fn node_reciprocity(graph: &HashMap<usize, HashSet<usize>>) -> HashMap<usize, f64> {
+    let followers = reverse_graph(graph);
+    let mut result = HashMap::new();
+
+    for (&node, node_followers) in &followers {
+        let followed = graph.get(&node);
+        let out_degree = followed.map_or(0, |f| f.iter().filter(|&&other| other != node).count());
+        let in_degree = node_followers.iter().filter(|&&other| other != node).count();
+        if in_degree + out_degree == 0 {
+            continue;
+        }
+        let mutual = followed.map_or(0, |f| f.iter().filter(|&&other| other != node && node_followers.contains(&other)).count());
+        result.insert(node, 2.0 * mutual as f64 / (in_degree + out_degree) as f64);
+    }
+
+    result
+}
+
+// Six-bit code of the arcs among v, u and w
+fn tricode(out: &[Vec<usize>], v: usize, u: usize, w: usize) -> usize {
+    [(v, u, 1), (u, v, 2), (v, w, 4), (w, v, 8), (u, w, 16), (w, u, 32)]
+        .iter()
+        .filter(|&&(from, to, _)| out[from].binary_search(&to).is_ok())
+        .map(|&(_, _, bit)| bit)
+        .sum()
+}
+
+// TRIAD CENSUS. Number of triples of accounts in each of the 16 directed triad classes (ordered as TRIAD_NAMES).
+// Batagelj and Mrvar's algorithm visits each connected triad once from its lowest-indexed edge, so the cost grows with edges times degree rather than n^3;
+// triads with no edges at all (003) are what remains of n choose 3.
+pub fn triad_census(graph: &HashMap<usize, HashSet<usize>>) -> [u64; 16] {
+    let (_, out) = index_graph(graph);
+    let n = out.len();
+
+    // Neighbors ignoring direction, without self-loops
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (v, targets) in out.iter().enumerate() {
+        for &u in targets.iter().filter(|&&u| u != v) {
+            neighbors[v].push(u);
+            neighbors[u].push(v);
+        }
+    }
+    for list in &mut neighbors {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    let mut census = [0u64; 16];
+    let mut union = Vec::new();
+    for v in 0..n {
+        for &u in neighbors[v].iter().filter(|&&u| u > v) {
+            // Union of both neighborhoods minus v and u: every other node is a third member with no ties to the pair
+            union.clear();
+            union.extend(neighbors[v].iter().chain(&neighbors[u]).filter(|&&w| w != v && w != u));
+            union.sort_unstable();
+            union.dedup();
+
+            let dyad = if out[v].binary_search(&u).is_ok() && out[u].binary_search(&v).is_ok() { 2 } else { 1 };
+            census[dyad] += (n - union.len() - 2) as u64;
+
+            // Count the triad only from its lowest edge
+            for &w in &union {
+                if u < w || (v < w && w < u && neighbors[v].binary_search(&w).is_err()) {
+                    census[TRICODES[tricode(&out, v, u, w)] - 1] += 1;
+                }
+            }
+        }
+    }
+
+    let n = n as u64;
+    let all_triads = if n < 3 { 0 } else { n * (n - 1) * (n - 2) / 6 };
+    census[0] = all_triads - census[1..].iter().sum::<u64>();
+    census
+}
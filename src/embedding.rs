@@ -0,0 +1,248 @@
+// Dense vector embeddings of accounts, learned from random walks. Accounts that show up near each other in many walks end up with similar vectors, which gives a similarity search over the whole graph and features for downstream ML.
+// DeepWalk uses uniform random walks; node2vec biases each step with a return parameter p and an in-out parameter q. Both feed the same skip-gram trainer with negative sampling.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::collections::HashSet;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+// Skip-gram training settings
+pub struct SkipGramSettings {
+    pub dimensions: usize,
+    pub window: usize,
+    pub negative: usize,
+    pub epochs: usize,
+    pub learning_rate: f32,
+}
+
+impl Default for SkipGramSettings {
+    fn default() -> Self {
+        SkipGramSettings {
+            dimensions: 64,
+            window: 5,
+            negative: 5,
+            epochs: 1,
+            learning_rate: 0.025,
+        }
+    }
+}
+
+// DEEPWALK WALKS. walks_per_node uniform random walks of up to walk_length nodes from every node (adjacency from index_graph)
+pub fn deepwalk_walks<R: Rng>(
+    adjacency: &[Vec<usize>],
+    walks_per_node: usize,
+    walk_length: usize,
+    rng: &mut R,
+) -> Vec<Vec<usize>> {
+    let mut starts: Vec<usize> = (0..adjacency.len()).collect();
+    let mut walks = Vec::with_capacity(adjacency.len() * walks_per_node);
+
+    for _ in 0..walks_per_node {
+        starts.shuffle(rng);
+        for &start in &starts {
+            let mut walk = vec![start];
+            while walk.len() < walk_length {
+                match adjacency[*walk.last().unwrap()].choose(rng) {
+                    Some(&next) => walk.push(next),
+                    None => break,
+                }
+            }
+            walks.push(walk);
+        }
+    }
+
+    walks
+}
+
+// NODE2VEC WALKS. Second-order random walks: returning to the previous node is weighted 1/p, staying next to it 1, and moving further away 1/q
+pub fn node2vec_walks<R: Rng>(
+    adjacency: &[Vec<usize>],
+    walks_per_node: usize,
+    walk_length: usize,
+    p: f64,
+    q: f64,
+    rng: &mut R,
+) -> Vec<Vec<usize>> {
+    // With no bias node2vec is exactly DeepWalk
+    if p == 1.0 && q == 1.0 {
+        return deepwalk_walks(adjacency, walks_per_node, walk_length, rng);
+    }
+
+    // Biased steps are drawn by rejection sampling against the largest of the three weights, which avoids precomputing alias tables for every edge
+    let max_weight = (1.0 / p).max(1.0).max(1.0 / q);
+    let mut starts: Vec<usize> = (0..adjacency.len()).collect();
+    let mut walks = Vec::with_capacity(adjacency.len() * walks_per_node);
+
+    for _ in 0..walks_per_node {
+        starts.shuffle(rng);
+        for &start in &starts {
+            let mut walk = vec![start];
+            while walk.len() < walk_length {
+                let current = *walk.last().unwrap();
+                if adjacency[current].is_empty() {
+                    break;
+                }
+                if walk.len() == 1 {
+                    walk.push(*adjacency[current].choose(rng).unwrap());
+                    continue;
+                }
+
+                let previous = walk[walk.len() - 2];
+                let next = loop {
+                    let candidate = *adjacency[current].choose(rng).unwrap();
+                    let weight = if candidate == previous {
+                        1.0 / p
+                    } else if adjacency[previous].binary_search(&candidate).is_ok() {
+                        1.0
+                    } else {
+                        1.0 / q
+                    };
+                    if rng.gen::<f64>() * max_weight < weight {
+                        break candidate;
+                    }
+                };
+                walk.push(next);
+            }
+            walks.push(walk);
+        }
+    }
+
+    walks
+}
+
+// SKIP-GRAM. Train one vector per node so that nodes appearing within `window` steps of each other in a walk score high, and randomly drawn negative samples score low
+pub fn train_skipgram<R: Rng>(
+    walks: &[Vec<usize>],
+    num_nodes: usize,
+    settings: &SkipGramSettings,
+    rng: &mut R,
+) -> Vec<Vec<f32>> {
+    let dimensions = settings.dimensions;
+    let mut input: Vec<Vec<f32>> = (0..num_nodes)
+        .map(|_| (0..dimensions).map(|_| (rng.gen::<f32>() - 0.5) / dimensions as f32).collect())
+        .collect();
+    let mut output = vec![vec![0.0f32; dimensions]; num_nodes];
+
+    // Negative samples are drawn from the node frequency distribution raised to the 3/4 power, as in word2vec
+    let mut frequency = vec![0usize; num_nodes];
+    for walk in walks {
+        for &node in walk {
+            frequency[node] += 1;
+        }
+    }
+    let mut cumulative = Vec::with_capacity(num_nodes);
+    let mut total = 0.0;
+    for &count in &frequency {
+        total += (count as f64).powf(0.75);
+        cumulative.push(total);
+    }
+    if total == 0.0 {
+        return input;
+    }
+
+    let total_steps = (settings.epochs * walks.len()).max(1) as f32;
+    let mut step = 0.0;
+    let mut hidden_error = vec![0.0f32; dimensions];
+
+    for _ in 0..settings.epochs {
+        for walk in walks {
+            // Learning rate decays linearly over training
+            let learning_rate = (settings.learning_rate * (1.0 - step / total_steps)).max(settings.learning_rate * 1e-4);
+            step += 1.0;
+
+            for (i, &center) in walk.iter().enumerate() {
+                // Sampling the window size weights nearby context nodes more heavily
+                let window = rng.gen_range(1..=settings.window.max(1));
+                let first = i.saturating_sub(window);
+                let last = (i + window).min(walk.len() - 1);
+
+                for (j, &context) in walk.iter().enumerate().take(last + 1).skip(first) {
+                    if j == i {
+                        continue;
+                    }
+                    hidden_error.iter_mut().for_each(|e| *e = 0.0);
+
+                    for sample in 0..=settings.negative {
+                        let (target, label) = if sample == 0 {
+                            (context, 1.0)
+                        } else {
+                            let draw = rng.gen::<f64>() * total;
+                            let target = cumulative.partition_point(|&c| c <= draw).min(num_nodes - 1);
+                            if target == context {
+                                continue;
+                            }
+                            (target, 0.0)
+                        };
+
+                        let dot: f32 = input[center].iter().zip(&output[target]).map(|(a, b)| a * b).sum();
+                        let gradient = (label - sigmoid(dot)) * learning_rate;
+                        for d in 0..dimensions {
+                            hidden_error[d] += gradient * output[target][d];
+                            output[target][d] += gradient * input[center][d];
+                        }
+                    }
+
+                    for (value, error) in input[center].iter_mut().zip(&hidden_error) {
+                        *value += error;
+                    }
+                }
+            }
+        }
+    }
+
+    input
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x.clamp(-6.0, 6.0)).exp())
+}
+
+// SAVE EMBEDDINGS. Write embeddings in the word2vec text format: a "count dimensions" header, then one "nodeID v1 v2 ..." line per node
+pub fn save_embeddings(file_path: &str, nodes: &[usize], embeddings: &[Vec<f32>]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(file_path)?);
+    let dimensions = embeddings.first().map_or(0, |v| v.len());
+    writeln!(writer, "{} {}", nodes.len(), dimensions)?;
+    for (node, vector) in nodes.iter().zip(embeddings) {
+        write!(writer, "{}", node)?;
+        for value in vector {
+            write!(writer, " {:.6}", value)?;
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+// MOST SIMILAR. The k nodes whose embeddings have the highest cosine similarity to the given node, skipping the node itself and anything in `exclude`
+pub fn most_similar(
+    nodes: &[usize],
+    embeddings: &[Vec<f32>],
+    node: usize,
+    exclude: &HashSet<usize>,
+    k: usize,
+) -> Vec<(usize, f32)> {
+    let index = match nodes.binary_search(&node) {
+        Ok(index) => index,
+        Err(_) => return Vec::new(),
+    };
+
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let target = &embeddings[index];
+    let target_norm = norm(target);
+
+    let mut similarities: Vec<(usize, f32)> = nodes
+        .iter()
+        .zip(embeddings)
+        .filter(|(&other, _)| other != node && !exclude.contains(&other))
+        .map(|(&other, vector)| {
+            let dot: f32 = target.iter().zip(vector).map(|(a, b)| a * b).sum();
+            let denominator = target_norm * norm(vector);
+            (other, if denominator > 0.0 { dot / denominator } else { 0.0 })
+        })
+        .collect();
+
+    similarities.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    similarities.truncate(k);
+
+    similarities
+}
@@ -0,0 +1,247 @@
+// Compartmental epidemic models on the follow network, e.g. for misinformation: an infected account exposes its followers (the same direction
+// as a tweet in diffusion.rs). Accounts are Susceptible, Infected or Recovered; in SIR recovery is permanent, in SIS recovered accounts become
+// susceptible again. Each run returns its (time, S, I, R) time series.
+// - discrete time: every step, each infected account infects each susceptible follower with probability infection_rate, then recovers with
+//   probability recovery_rate
+// - continuous time (Gillespie): each edge from an infected account to a susceptible follower transmits at rate infection_rate and each
+//   infected account recovers at rate recovery_rate. Every infected account's number of susceptible followers is kept in a Fenwick tree, so the
+//   total transmission rate is exact and the source of the next transmission is drawn in logarithmic time however skewed the follower counts.
+
+use rand::Rng;
+use crate::diffusion::DiffusionGraph;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EpidemicModel {
+    Sir,
+    Sis,
+}
+
+#[derive(Clone, Copy)]
+pub struct EpidemicSettings {
+    pub model: EpidemicModel,
+    pub infection_rate: f64,
+    pub recovery_rate: f64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Susceptible,
+    Infected,
+    Recovered,
+}
+
+// Fenwick tree over per-node weights, for drawing a node with probability proportional to its weight
+struct WeightTree {
+    tree: Vec<i64>,
+    total: i64,
+}
+
+impl WeightTree {
+    fn new(n: usize) -> Self {
+        WeightTree { tree: vec![0; n + 1], total: 0 }
+    }
+
+    fn add(&mut self, index: usize, delta: i64) {
+        self.total += delta;
+        let mut position = index + 1;
+        while position < self.tree.len() {
+            self.tree[position] += delta;
+            position += position & position.wrapping_neg();
+        }
+    }
+
+    // The node whose weight covers `target` in 0..total, walking down the tree from the largest power of two
+    fn find(&self, mut target: i64) -> usize {
+        let mut position = 0;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            if position + step < self.tree.len() && self.tree[position + step] <= target {
+                position += step;
+                target -= self.tree[position];
+            }
+            step /= 2;
+        }
+        position
+    }
+}
+
+// Counts of each compartment
+fn counts(states: &[State]) -> (usize, usize, usize) {
+    let infected = states.iter().filter(|&&s| s == State::Infected).count();
+    let recovered = states.iter().filter(|&&s| s == State::Recovered).count();
+    (states.len() - infected - recovered, infected, recovered)
+}
+
+fn initial_states(n: usize, initial: &[usize]) -> Vec<State> {
+    let mut states = vec![State::Susceptible; n];
+    for &node in initial {
+        states[node] = State::Infected;
+    }
+    states
+}
+
+// DISCRETE-TIME EPIDEMIC. Synchronous steps from the initially infected indices until nobody is infected or max_steps have passed.
+// Returns (step, S, I, R) after every step, starting with step 0.
+pub fn simulate_discrete<R: Rng>(
+    graph: &DiffusionGraph,
+    settings: EpidemicSettings,
+    initial: &[usize],
+    max_steps: usize,
+    rng: &mut R,
+) -> Vec<(f64, usize, usize, usize)> {
+    let mut states = initial_states(graph.nodes.len(), initial);
+    let mut infected: Vec<usize> = (0..states.len()).filter(|&node| states[node] == State::Infected).collect();
+    let (s, i, r) = counts(&states);
+    let mut series = vec![(0.0, s, i, r)];
+
+    for step in 1..=max_steps {
+        if infected.is_empty() {
+            break;
+        }
+        // Infections happen against the state at the start of the step
+        let mut newly_infected = Vec::new();
+        for &node in &infected {
+            for &follower in &graph.followers[node] {
+                if states[follower] == State::Susceptible && rng.gen::<f64>() < settings.infection_rate {
+                    states[follower] = State::Infected;
+                    newly_infected.push(follower);
+                }
+            }
+        }
+
+        let mut still_infected = Vec::with_capacity(infected.len() + newly_infected.len());
+        for node in infected {
+            if rng.gen::<f64>() < settings.recovery_rate {
+                states[node] = match settings.model {
+                    EpidemicModel::Sir => State::Recovered,
+                    EpidemicModel::Sis => State::Susceptible,
+                };
+            } else {
+                still_infected.push(node);
+            }
+        }
+        still_infected.extend(newly_infected);
+        infected = still_infected;
+
+        let (s, i, r) = counts(&states);
+        series.push((step as f64, s, i, r));
+    }
+
+    series
+}
+
+// GILLESPIE EPIDEMIC. Continuous-time simulation until nobody is infected, no event can happen any more (no susceptible follower of an
+// infected account and no recovery), or max_time is reached, recording (time, S, I, R) every report_interval time units (and at the end).
+pub fn simulate_gillespie<R: Rng>(
+    graph: &DiffusionGraph,
+    settings: EpidemicSettings,
+    initial: &[usize],
+    max_time: f64,
+    report_interval: f64,
+    rng: &mut R,
+) -> Vec<(f64, usize, usize, usize)> {
+    let n = graph.nodes.len();
+    let mut states = initial_states(n, initial);
+    // Infected accounts, with each one's position in the list for O(1) removal
+    let mut infected: Vec<usize> = Vec::new();
+    let mut position = vec![usize::MAX; n];
+    // Number of susceptible followers of each infected account (0 for everyone else)
+    let mut exposed = WeightTree::new(n);
+    let susceptible_followers =
+        |states: &[State], node: usize| graph.followers[node].iter().filter(|&&f| states[f] == State::Susceptible).count() as i64;
+    for node in 0..n {
+        if states[node] == State::Infected {
+            position[node] = infected.len();
+            infected.push(node);
+            exposed.add(node, susceptible_followers(&states, node));
+        }
+    }
+
+    let (mut s, mut i, mut r) = counts(&states);
+    let mut series = vec![(0.0, s, i, r)];
+    let mut next_report = report_interval;
+    let mut time = 0.0;
+
+    while !infected.is_empty() {
+        let transmission_rate = settings.infection_rate * exposed.total as f64;
+        let recovery_rate = settings.recovery_rate * infected.len() as f64;
+        let total_rate = transmission_rate + recovery_rate;
+        if total_rate <= 0.0 {
+            break;
+        }
+        time += -(1.0 - rng.gen::<f64>()).ln() / total_rate;
+        if time > max_time {
+            break;
+        }
+        while next_report <= time {
+            series.push((next_report, s, i, r));
+            next_report += report_interval;
+        }
+
+        if rng.gen::<f64>() * total_rate < recovery_rate {
+            let node = infected[rng.gen_range(0..infected.len())];
+            let index = position[node];
+            infected.swap_remove(index);
+            if index < infected.len() {
+                position[infected[index]] = index;
+            }
+            position[node] = usize::MAX;
+            exposed.add(node, -susceptible_followers(&states, node));
+            i -= 1;
+            match settings.model {
+                EpidemicModel::Sir => {
+                    states[node] = State::Recovered;
+                    r += 1;
+                }
+                EpidemicModel::Sis => {
+                    states[node] = State::Susceptible;
+                    s += 1;
+                    // Infected accounts it follows can reach it again
+                    for &source in &graph.following[node] {
+                        if states[source] == State::Infected {
+                            exposed.add(source, 1);
+                        }
+                    }
+                }
+            }
+        } else {
+            // An infected-susceptible edge uniformly: its source in proportion to its susceptible followers, then one of those followers
+            let source = exposed.find(rng.gen_range(0..exposed.total));
+            let mut choice = rng.gen_range(0..susceptible_followers(&states, source));
+            let follower = *graph.followers[source]
+                .iter()
+                .find(|&&f| {
+                    if states[f] != State::Susceptible {
+                        return false;
+                    }
+                    choice -= 1;
+                    choice < 0
+                })
+                .unwrap();
+
+            states[follower] = State::Infected;
+            position[follower] = infected.len();
+            infected.push(follower);
+            s -= 1;
+            i += 1;
+            // The new case is no longer exposed to the infected accounts it follows, and now exposes its own susceptible followers.
+            // A self-follow was never counted: the account was not susceptible while it was infected
+            for &source in &graph.following[follower] {
+                if source != follower && states[source] == State::Infected {
+                    exposed.add(source, -1);
+                }
+            }
+            exposed.add(follower, susceptible_followers(&states, follower));
+        }
+    }
+
+    let end = time.min(max_time);
+    while next_report <= end {
+        series.push((next_report, s, i, r));
+        next_report += report_interval;
+    }
+    if series.last().is_some_and(|&(t, ..)| t < end) {
+        series.push((end, s, i, r));
+    }
+    series
+}
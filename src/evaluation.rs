@@ -0,0 +1,189 @@
+// Scores for comparing a detected community structure with a reference one, such as the SNAP ground-truth circles.
+// Partitions (every node in exactly one community) are maps from node ID to community ID; covers (nodes may be in several communities, or none) are lists of member sets.
+
+use std::collections::{HashMap, HashSet};
+
+// PARTITION TO COMMUNITIES. List the member set of each community in a partition
+pub fn partition_to_communities(partition: &HashMap<usize, usize>) -> Vec<HashSet<usize>> {
+    let mut members: HashMap<usize, HashSet<usize>> = HashMap::new();
+    for (&node, &community) in partition {
+        members.entry(community).or_default().insert(node);
+    }
+    let mut communities: Vec<(usize, HashSet<usize>)> = members.into_iter().collect();
+    communities.sort_by_key(|(community, _)| *community);
+    communities.into_iter().map(|(_, nodes)| nodes).collect()
+}
+
+// COVER TO COMMUNITIES. List the member set of each community in a cover (node ID to community IDs), in community ID order
+pub fn cover_to_communities(cover: &HashMap<usize, Vec<usize>>) -> Vec<HashSet<usize>> {
+    let mut members: HashMap<usize, HashSet<usize>> = HashMap::new();
+    for (&node, communities) in cover {
+        for &community in communities {
+            members.entry(community).or_default().insert(node);
+        }
+    }
+    let mut communities: Vec<(usize, HashSet<usize>)> = members.into_iter().collect();
+    communities.sort_by_key(|(community, _)| *community);
+    communities.into_iter().map(|(_, nodes)| nodes).collect()
+}
+
+// COMMUNITIES TO PARTITION. Turn a cover into a partition by assigning each node to the largest community that contains it (the first one on ties); nodes in no community are left out
+pub fn communities_to_partition(communities: &[HashSet<usize>]) -> HashMap<usize, usize> {
+    let mut partition: HashMap<usize, usize> = HashMap::new();
+    for (id, members) in communities.iter().enumerate() {
+        for &node in members {
+            let replace = partition.get(&node).is_none_or(|&current| communities[current].len() < members.len());
+            if replace {
+                partition.insert(node, id);
+            }
+        }
+    }
+    partition
+}
+
+// Contingency table between two partitions over the nodes they share
+struct Contingency {
+    joint: HashMap<(usize, usize), f64>,
+    a_counts: HashMap<usize, f64>,
+    b_counts: HashMap<usize, f64>,
+    n: f64,
+}
+
+fn contingency(a: &HashMap<usize, usize>, b: &HashMap<usize, usize>) -> Contingency {
+    let mut table = Contingency { joint: HashMap::new(), a_counts: HashMap::new(), b_counts: HashMap::new(), n: 0.0 };
+    for (node, &a_community) in a {
+        if let Some(&b_community) = b.get(node) {
+            *table.joint.entry((a_community, b_community)).or_insert(0.0) += 1.0;
+            *table.a_counts.entry(a_community).or_insert(0.0) += 1.0;
+            *table.b_counts.entry(b_community).or_insert(0.0) += 1.0;
+            table.n += 1.0;
+        }
+    }
+    table
+}
+
+// NORMALIZED MUTUAL INFORMATION. Mutual information between two partitions divided by the mean of their entropies, over the nodes they share: 1 for identical partitions, near 0 for unrelated ones
+pub fn normalized_mutual_information(a: &HashMap<usize, usize>, b: &HashMap<usize, usize>) -> f64 {
+    let Contingency { joint, a_counts, b_counts, n } = contingency(a, b);
+    if n == 0.0 {
+        return 0.0;
+    }
+    let entropy = |counts: &HashMap<usize, f64>| -> f64 {
+        counts.values().map(|&c| -(c / n) * (c / n).ln()).sum()
+    };
+    let mutual_information: f64 = joint
+        .iter()
+        .map(|(&(i, j), &c)| (c / n) * (c * n / (a_counts[&i] * b_counts[&j])).ln())
+        .sum();
+
+    let mean_entropy = (entropy(&a_counts) + entropy(&b_counts)) / 2.0;
+    if mean_entropy == 0.0 {
+        // Both partitions put everything in one community
+        return 1.0;
+    }
+    (mutual_information / mean_entropy).clamp(0.0, 1.0)
+}
+
+// ADJUSTED RAND INDEX. Agreement on which pairs of nodes share a community, corrected for chance: 1 for identical partitions, around 0 for random ones
+pub fn adjusted_rand_index(a: &HashMap<usize, usize>, b: &HashMap<usize, usize>) -> f64 {
+    let Contingency { joint, a_counts, b_counts, n } = contingency(a, b);
+    let pairs = |c: f64| c * (c - 1.0) / 2.0;
+
+    let index: f64 = joint.values().map(|&c| pairs(c)).sum();
+    let a_pairs: f64 = a_counts.values().map(|&c| pairs(c)).sum();
+    let b_pairs: f64 = b_counts.values().map(|&c| pairs(c)).sum();
+    let total_pairs = pairs(n);
+    if total_pairs == 0.0 {
+        return 1.0;
+    }
+
+    let expected = a_pairs * b_pairs / total_pairs;
+    let maximum = (a_pairs + b_pairs) / 2.0;
+    if maximum == expected {
+        return 1.0;
+    }
+    (index - expected) / (maximum - expected)
+}
+
+// F1 score between two node sets
+fn f1_score(a: &HashSet<usize>, b: &HashSet<usize>) -> f64 {
+    let overlap = a.intersection(b).count() as f64;
+    if overlap == 0.0 {
+        return 0.0;
+    }
+    let precision = overlap / a.len() as f64;
+    let recall = overlap / b.len() as f64;
+    2.0 * precision * recall / (precision + recall)
+}
+
+// BEST-MATCH F1. Match every detected community to its best ground-truth community and vice versa, then average the two mean F1 scores
+pub fn best_match_f1(detected: &[HashSet<usize>], truth: &[HashSet<usize>]) -> f64 {
+    if detected.is_empty() || truth.is_empty() {
+        return 0.0;
+    }
+    let average_best = |from: &[HashSet<usize>], to: &[HashSet<usize>]| -> f64 {
+        from.iter()
+            .map(|community| to.iter().map(|other| f1_score(community, other)).fold(0.0, f64::max))
+            .sum::<f64>()
+            / from.len() as f64
+    };
+    (average_best(detected, truth) + average_best(truth, detected)) / 2.0
+}
+
+// Number of communities each pair of nodes shares, for every pair that shares at least one
+fn shared_community_counts(cover: &[HashSet<usize>], nodes: &HashSet<usize>) -> HashMap<(usize, usize), usize> {
+    let mut counts = HashMap::new();
+    for community in cover {
+        let mut members: Vec<usize> = community.intersection(nodes).cloned().collect();
+        members.sort_unstable();
+        for (i, &u) in members.iter().enumerate() {
+            for &v in &members[i + 1..] {
+                *counts.entry((u, v)).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+// OMEGA INDEX. Overlapping generalization of the adjusted Rand index: two covers agree on a pair of nodes when the pair shares the same number of communities in both. Only the given nodes are considered.
+pub fn omega_index(a: &[HashSet<usize>], b: &[HashSet<usize>], nodes: &HashSet<usize>) -> f64 {
+    let n = nodes.len() as f64;
+    let total_pairs = n * (n - 1.0) / 2.0;
+    if total_pairs == 0.0 {
+        return 1.0;
+    }
+    let a_counts = shared_community_counts(a, nodes);
+    let b_counts = shared_community_counts(b, nodes);
+
+    // Pairs that share no community in either cover agree too
+    let mut agreements = 0.0;
+    for (pair, &count) in &a_counts {
+        if b_counts.get(pair) == Some(&count) {
+            agreements += 1.0;
+        }
+    }
+    let both_zero = total_pairs - a_counts.len() as f64 - b_counts.keys().filter(|pair| !a_counts.contains_key(pair)).count() as f64;
+    agreements += both_zero;
+    let observed = agreements / total_pairs;
+
+    // Chance agreement from how many pairs share each number of communities
+    let histogram = |counts: &HashMap<(usize, usize), usize>| -> HashMap<usize, f64> {
+        let mut histogram: HashMap<usize, f64> = HashMap::from([(0, total_pairs - counts.len() as f64)]);
+        for &count in counts.values() {
+            *histogram.entry(count).or_insert(0.0) += 1.0;
+        }
+        histogram
+    };
+    let a_histogram = histogram(&a_counts);
+    let b_histogram = histogram(&b_counts);
+    let expected: f64 = a_histogram
+        .iter()
+        .map(|(count, &pairs)| pairs * b_histogram.get(count).copied().unwrap_or(0.0))
+        .sum::<f64>()
+        / (total_pairs * total_pairs);
+
+    if expected == 1.0 {
+        return 1.0;
+    }
+    (observed - expected) / (1.0 - expected)
+}
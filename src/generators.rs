@@ -0,0 +1,248 @@
+// Random graph generators, used as baselines for the Twitter statistics: if a random graph with the same size (or the same degrees, or the same
+// communities) shows the same numbers, the numbers say little about Twitter itself. Every generator takes a seeded rng and returns an undirected
+// graph in the loader's format, on nodes 0..n, with every node present as a key (isolated nodes have an empty set).
+
+use std::collections::{HashMap, HashSet};
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+fn empty_graph(n: usize) -> HashMap<usize, HashSet<usize>> {
+    (0..n).map(|node| (node, HashSet::new())).collect()
+}
+
+// Add an undirected edge; returns false for self-loops and edges that already exist
+fn add_edge(graph: &mut HashMap<usize, HashSet<usize>>, a: usize, b: usize) -> bool {
+    if a == b || !graph.get_mut(&a).unwrap().insert(b) {
+        return false;
+    }
+    graph.get_mut(&b).unwrap().insert(a);
+    true
+}
+
+// Call visit for each index in 0..total chosen independently with probability p, jumping between chosen indices with geometric gaps
+// (Batagelj and Brandes) so the cost is proportional to the number chosen rather than to total
+fn geometric_sample<R: Rng, F: FnMut(u64)>(total: u64, p: f64, rng: &mut R, mut visit: F) {
+    if p <= 0.0 {
+        return;
+    }
+    if p >= 1.0 {
+        (0..total).for_each(visit);
+        return;
+    }
+    let log_q = (-p).ln_1p();
+    let mut index: i64 = -1;
+    loop {
+        let r: f64 = rng.gen();
+        index = index.saturating_add(1 + ((1.0 - r).ln() / log_q).floor() as i64);
+        if index as u64 >= total {
+            break;
+        }
+        visit(index as u64);
+    }
+}
+
+// The pair (a, b), a < b, at position `index` of the row-by-row list (0, 1), (0, 2), ..., (1, 2), ...
+fn pair_from_index(index: u64, n: u64) -> (usize, usize) {
+    // Row a starts at a * (2n - a - 1) / 2; solve for the last row starting at or before index
+    let nf = n as f64;
+    let mut a = ((2.0 * nf - 1.0 - ((2.0 * nf - 1.0).powi(2) - 8.0 * index as f64).max(0.0).sqrt()) / 2.0).floor() as u64;
+    let row_start = |a: u64| a * (2 * n - a - 1) / 2;
+    // Floating-point error can leave the estimate one row off
+    while a > 0 && row_start(a) > index {
+        a -= 1;
+    }
+    while row_start(a + 1) <= index {
+        a += 1;
+    }
+    let b = a + 1 + (index - row_start(a));
+    (a as usize, b as usize)
+}
+
+// ERDŐS–RÉNYI G(n, p). Every pair of nodes is linked independently with probability p
+pub fn erdos_renyi_gnp<R: Rng>(n: usize, p: f64, rng: &mut R) -> HashMap<usize, HashSet<usize>> {
+    let mut graph = empty_graph(n);
+    let pairs = (n as u64) * (n as u64).saturating_sub(1) / 2;
+    geometric_sample(pairs, p, rng, |index| {
+        let (a, b) = pair_from_index(index, n as u64);
+        add_edge(&mut graph, a, b);
+    });
+    graph
+}
+
+// ERDŐS–RÉNYI G(n, m). m edges chosen uniformly among all pairs (capped at the number of pairs)
+pub fn erdos_renyi_gnm<R: Rng>(n: usize, m: usize, rng: &mut R) -> HashMap<usize, HashSet<usize>> {
+    let mut graph = empty_graph(n);
+    let pairs = n * n.saturating_sub(1) / 2;
+    let m = m.min(pairs);
+    // Past half of all pairs it is faster to choose the pairs to leave out
+    if m > pairs / 2 {
+        let complement = erdos_renyi_gnm(n, pairs - m, rng);
+        for a in 0..n {
+            for b in (a + 1)..n {
+                if !complement[&a].contains(&b) {
+                    add_edge(&mut graph, a, b);
+                }
+            }
+        }
+        return graph;
+    }
+
+    let mut added = 0;
+    while added < m {
+        let a = rng.gen_range(0..n);
+        let b = rng.gen_range(0..n);
+        if add_edge(&mut graph, a, b) {
+            added += 1;
+        }
+    }
+    graph
+}
+
+// BARABÁSI–ALBERT. Preferential attachment: starting from m unconnected nodes, each new node links to m distinct existing nodes chosen with
+// probability proportional to their degree
+pub fn barabasi_albert<R: Rng>(n: usize, m: usize, rng: &mut R) -> HashMap<usize, HashSet<usize>> {
+    let mut graph = empty_graph(n);
+    if m == 0 || n <= m {
+        return graph;
+    }
+
+    // Every node appears once per edge end, so a uniform draw from this list is a degree-proportional draw
+    let mut repeated_nodes: Vec<usize> = Vec::with_capacity(2 * n * m);
+    let mut targets: Vec<usize> = (0..m).collect();
+    for source in m..n {
+        for &target in &targets {
+            add_edge(&mut graph, source, target);
+        }
+        repeated_nodes.extend(&targets);
+        repeated_nodes.extend(std::iter::repeat_n(source, m));
+
+        let mut chosen = HashSet::with_capacity(m);
+        targets.clear();
+        while targets.len() < m {
+            let target = *repeated_nodes.choose(rng).unwrap();
+            if chosen.insert(target) {
+                targets.push(target);
+            }
+        }
+    }
+    graph
+}
+
+// WATTS–STROGATZ. A ring where each node links to its k nearest neighbors (k / 2 on each side, k even), after which each edge's far end is
+// rewired to a uniformly random node with probability p, avoiding self-loops and duplicate edges
+pub fn watts_strogatz<R: Rng>(n: usize, k: usize, p: f64, rng: &mut R) -> HashMap<usize, HashSet<usize>> {
+    let mut graph = empty_graph(n);
+    let half = (k / 2).min(n.saturating_sub(1) / 2);
+    for node in 0..n {
+        for offset in 1..=half {
+            add_edge(&mut graph, node, (node + offset) % n);
+        }
+    }
+
+    for offset in 1..=half {
+        for node in 0..n {
+            let neighbor = (node + offset) % n;
+            if rng.gen::<f64>() >= p || graph[&node].len() >= n - 1 {
+                continue;
+            }
+            let new_neighbor = loop {
+                let candidate = rng.gen_range(0..n);
+                if candidate != node && !graph[&node].contains(&candidate) {
+                    break candidate;
+                }
+            };
+            graph.get_mut(&node).unwrap().remove(&neighbor);
+            graph.get_mut(&neighbor).unwrap().remove(&node);
+            add_edge(&mut graph, node, new_neighbor);
+        }
+    }
+    graph
+}
+
+// CHUNG–LU. Nodes i and j are linked with probability min(w_i w_j / sum(w), 1), so expected degrees follow the weights (e.g. an observed degree
+// sequence). Uses Miller and Hagberg's algorithm, which skips over unlikely pairs and runs in time proportional to nodes plus edges.
+pub fn chung_lu<R: Rng>(weights: &[f64], rng: &mut R) -> HashMap<usize, HashSet<usize>> {
+    let n = weights.len();
+    let mut graph = empty_graph(n);
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return graph;
+    }
+
+    // Visiting partners in descending weight order makes the link probability non-increasing along each row
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| weights[b].total_cmp(&weights[a]));
+
+    for u in 0..n {
+        let weight_u = weights[order[u]];
+        let mut v = u + 1;
+        let mut p = if v < n { (weight_u * weights[order[v]] / total).min(1.0) } else { 0.0 };
+        while v < n && p > 0.0 {
+            if p < 1.0 {
+                let r: f64 = rng.gen();
+                v = v.saturating_add(((1.0 - r).ln() / (-p).ln_1p()).floor() as usize);
+            }
+            if v < n {
+                let q = (weight_u * weights[order[v]] / total).min(1.0);
+                if rng.gen::<f64>() < q / p {
+                    add_edge(&mut graph, order[u], order[v]);
+                }
+                p = q;
+                v += 1;
+            }
+        }
+    }
+    graph
+}
+
+// CONFIGURATION MODEL. Random matching of degree "stubs", so node i gets degree degrees[i]. Self-loops and repeated edges that the matching
+// creates are dropped (the "erased" configuration model), which lowers the degrees of the largest hubs slightly; with an odd total one stub
+// stays unmatched.
+pub fn configuration_model<R: Rng>(degrees: &[usize], rng: &mut R) -> HashMap<usize, HashSet<usize>> {
+    let mut graph = empty_graph(degrees.len());
+    let mut stubs: Vec<usize> = degrees
+        .iter()
+        .enumerate()
+        .flat_map(|(node, &degree)| std::iter::repeat_n(node, degree))
+        .collect();
+    stubs.shuffle(rng);
+    for pair in stubs.chunks_exact(2) {
+        add_edge(&mut graph, pair[0], pair[1]);
+    }
+    graph
+}
+
+// STOCHASTIC BLOCK MODEL. Nodes are split into consecutive blocks of the given sizes, and a node in block i links to a node in block j with
+// probability probabilities[i][j] (the matrix should be symmetric)
+pub fn stochastic_block_model<R: Rng>(
+    sizes: &[usize],
+    probabilities: &[Vec<f64>],
+    rng: &mut R,
+) -> HashMap<usize, HashSet<usize>> {
+    let n: usize = sizes.iter().sum();
+    let mut graph = empty_graph(n);
+    let starts: Vec<usize> = sizes.iter().scan(0, |start, &size| {
+        let block_start = *start;
+        *start += size;
+        Some(block_start)
+    }).collect();
+
+    for i in 0..sizes.len() {
+        for j in i..sizes.len() {
+            let (start_i, start_j) = (starts[i], starts[j]);
+            if i == j {
+                let size = sizes[i] as u64;
+                geometric_sample(size * size.saturating_sub(1) / 2, probabilities[i][i], rng, |index| {
+                    let (a, b) = pair_from_index(index, size);
+                    add_edge(&mut graph, start_i + a, start_i + b);
+                });
+            } else {
+                let columns = sizes[j] as u64;
+                geometric_sample(sizes[i] as u64 * columns, probabilities[i][j], rng, |index| {
+                    add_edge(&mut graph, start_i + (index / columns) as usize, start_j + (index % columns) as usize);
+                });
+            }
+        }
+    }
+    graph
+}
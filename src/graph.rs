@@ -1,11 +1,13 @@
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
-use rand::seq::IteratorRandom;
+use rand::Rng;
+use rand::seq::{IteratorRandom, SliceRandom};
 
 // Utility function to read lines from a file
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
 {
@@ -14,6 +16,8 @@ where
 }
 
 // READ GRAPH. Read the graph from the dataset
+// Unreadable lines are skipped rather than ending the read
+#[allow(clippy::manual_flatten)]
 pub fn read_graph(file_path: &str) -> HashMap<usize, HashSet<usize>> {
     let mut graph = HashMap::new();
     if let Ok(lines) = read_lines(file_path) {
@@ -32,6 +36,47 @@ pub fn read_graph(file_path: &str) -> HashMap<usize, HashSet<usize>> {
     graph
 }
 
+// READ DIRECTED GRAPH. Read the dataset keeping edge direction: each line "a b" means a follows b, so the map goes from a follower to the accounts they follow
+#[allow(clippy::manual_flatten)]
+pub fn read_directed_graph(file_path: &str) -> HashMap<usize, HashSet<usize>> {
+    let mut graph = HashMap::new();
+    if let Ok(lines) = read_lines(file_path) {
+        for line in lines {
+            if let Ok(edge) = line {
+                let nodes: Vec<usize> = edge.split_whitespace()
+                    .filter_map(|s| s.parse::<usize>().ok())
+                    .collect();
+                if nodes.len() == 2 {
+                    graph.entry(nodes[0]).or_insert_with(HashSet::new).insert(nodes[1]);
+                    // Accounts that follow nobody still get an (empty) entry
+                    graph.entry(nodes[1]).or_insert_with(HashSet::new);
+                }
+            }
+        }
+    }
+    graph
+}
+
+// REVERSE GRAPH. Flip every edge of a directed graph, e.g. turning "who each account follows" into "who follows each account"
+pub fn reverse_graph(graph: &HashMap<usize, HashSet<usize>>) -> HashMap<usize, HashSet<usize>> {
+    let mut reversed: HashMap<usize, HashSet<usize>> = graph.keys().map(|&node| (node, HashSet::new())).collect();
+    for (&node, neighbors) in graph {
+        for &neighbor in neighbors {
+            reversed.entry(neighbor).or_default().insert(node);
+        }
+    }
+    reversed
+}
+
+// UNDIRECTED GRAPH. Forget edge direction: each account is linked to everyone it follows or is followed by
+pub fn to_undirected(graph: &HashMap<usize, HashSet<usize>>) -> HashMap<usize, HashSet<usize>> {
+    let mut undirected = reverse_graph(graph);
+    for (&node, neighbors) in graph {
+        undirected.entry(node).or_default().extend(neighbors);
+    }
+    undirected
+}
+
 // RANDOMLY SAMPLE. Randomly sample nodes and build a subgraph
 pub fn sample_graph(
     graph: &HashMap<usize, HashSet<usize>>,
@@ -41,16 +86,68 @@ pub fn sample_graph(
         .choose_multiple(&mut rand::thread_rng(), sample_size)
         .into_iter()
         .collect();
-    let mut sampled_graph = HashMap::new();
-    for &node in &sampled_nodes {
+    let sampled_graph = induced_subgraph(graph, &sampled_nodes);
+    (sampled_graph, sampled_nodes)
+}
+
+// INDUCED SUBGRAPH. Keep only the edges between the given nodes (nodes left without neighbors are dropped)
+pub fn induced_subgraph(
+    graph: &HashMap<usize, HashSet<usize>>,
+    nodes: &HashSet<usize>,
+) -> HashMap<usize, HashSet<usize>> {
+    let mut subgraph = HashMap::new();
+    for &node in nodes {
         if let Some(neighbors) = graph.get(&node) {
-            let filtered_neighbors: HashSet<usize> = neighbors.intersection(&sampled_nodes).cloned().collect();
+            let filtered_neighbors: HashSet<usize> = neighbors.intersection(nodes).cloned().collect();
             if !filtered_neighbors.is_empty() {
-                sampled_graph.insert(node, filtered_neighbors);
+                subgraph.insert(node, filtered_neighbors);
             }
         }
     }
-    (sampled_graph, sampled_nodes)
+    subgraph
+}
+
+// K-HOP NEIGHBORHOOD. Every node within k hops of the given node, with its distance (the node itself is at distance 0)
+pub fn k_hop_neighborhood(
+    graph: &HashMap<usize, HashSet<usize>>,
+    node: usize,
+    k: usize,
+) -> HashMap<usize, usize> {
+    let mut distances = HashMap::new();
+    if !graph.contains_key(&node) {
+        return distances;
+    }
+    let mut queue = std::collections::VecDeque::new();
+    distances.insert(node, 0);
+    queue.push_back(node);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[&current];
+        if distance == k {
+            continue;
+        }
+        if let Some(neighbors) = graph.get(&current) {
+            for &neighbor in neighbors {
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    entry.insert(distance + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+// EGO NETWORK. The subgraph induced by a node and everything within `radius` hops of it, in the same shape sample_graph returns so the other metrics can run on it
+pub fn ego_network(
+    graph: &HashMap<usize, HashSet<usize>>,
+    node: usize,
+    radius: usize,
+) -> (HashMap<usize, HashSet<usize>>, HashSet<usize>) {
+    let ego_nodes: HashSet<usize> = k_hop_neighborhood(graph, node, radius).into_keys().collect();
+    let ego_graph = induced_subgraph(graph, &ego_nodes);
+    (ego_graph, ego_nodes)
 }
 
 // ANALYZE. Analyze the graph and compute statistics
@@ -98,6 +195,16 @@ pub fn compute_avg_degrees_of_separation(
     total_distance as f64 / num_reachable_nodes as f64
 }
 
+// MEAN DISTANCE. Average distance from a node to the other nodes it reaches (0 if it reaches none). Unlike the degrees of separation
+// above, the node itself is not counted as a path of length 0
+pub fn mean_distance(graph: &HashMap<usize, HashSet<usize>>, start: usize) -> f64 {
+    let distances = k_hop_neighborhood(graph, start, usize::MAX);
+    if distances.len() < 2 {
+        return 0.0;
+    }
+    distances.values().sum::<usize>() as f64 / (distances.len() - 1) as f64
+}
+
 // DEGREE CENTRALITY. Calculate degree centrality for most inflential profiles - most neighbors
 
 pub fn degree_centrality(graph: &HashMap<usize, HashSet<usize>>) -> HashMap<usize, usize> {
@@ -139,8 +246,164 @@ pub fn most_shared_neighbors(
         })
         .collect();
 
+    #[allow(clippy::unnecessary_sort_by)]
     shared_counts.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by shared neighbor count, descending
     shared_counts.truncate(5); // Keep top 5
 
     shared_counts
 }
+
+// INDEX GRAPH. Relabel the nodes as 0..n (in ascending ID order) with sorted adjacency lists, for algorithms that work on dense vectors instead of hash maps
+pub fn index_graph(graph: &HashMap<usize, HashSet<usize>>) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let mut nodes: Vec<usize> = graph.keys().cloned().collect();
+    // Neighbors that never appear as a key (e.g. accounts that follow nobody in a directed graph) still need an index
+    for neighbors in graph.values() {
+        nodes.extend(neighbors.iter().filter(|n| !graph.contains_key(n)));
+    }
+    nodes.sort_unstable();
+    nodes.dedup();
+
+    let index: HashMap<usize, usize> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+    let adjacency = nodes
+        .iter()
+        .map(|node| {
+            let mut neighbors: Vec<usize> = graph
+                .get(node)
+                .map(|neighbors| neighbors.iter().map(|n| index[n]).collect())
+                .unwrap_or_default();
+            neighbors.sort_unstable();
+            neighbors
+        })
+        .collect();
+
+    (nodes, adjacency)
+}
+
+// PAGERANK. Global PageRank by power iteration: a walker follows a random edge with probability damping and jumps to a random node otherwise.
+// On a follow graph, rank flows from each follower to the accounts they follow; walkers at accounts that follow nobody jump anywhere.
+pub fn pagerank(
+    graph: &HashMap<usize, HashSet<usize>>,
+    damping: f64,
+    iterations: usize,
+) -> HashMap<usize, f64> {
+    let (nodes, adjacency) = index_graph(graph);
+    let n = nodes.len() as f64;
+    let mut scores = vec![1.0 / n; nodes.len()];
+
+    for _ in 0..iterations {
+        let mut next = vec![0.0; nodes.len()];
+        let mut dangling = 0.0;
+        for (node, neighbors) in adjacency.iter().enumerate() {
+            if neighbors.is_empty() {
+                dangling += scores[node];
+                continue;
+            }
+            let share = damping * scores[node] / neighbors.len() as f64;
+            for &neighbor in neighbors {
+                next[neighbor] += share;
+            }
+        }
+        let jump = (1.0 - damping + damping * dangling) / n;
+        next.iter_mut().for_each(|score| *score += jump);
+
+        // Stop early once the scores have settled
+        let change: f64 = scores.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < 1e-10 {
+            break;
+        }
+    }
+
+    nodes.into_iter().zip(scores).collect()
+}
+
+// CONNECTED COMPONENTS. Components of an undirected graph, largest first (each sorted by node ID)
+pub fn connected_components(graph: &HashMap<usize, HashSet<usize>>) -> Vec<Vec<usize>> {
+    let mut nodes: Vec<usize> = graph.keys().cloned().collect();
+    nodes.sort_unstable();
+    let mut visited = HashSet::new();
+    let mut components = Vec::new();
+
+    for start in nodes {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut component = vec![start];
+        let mut index = 0;
+        while index < component.len() {
+            if let Some(neighbors) = graph.get(&component[index]) {
+                for &neighbor in neighbors {
+                    if visited.insert(neighbor) {
+                        component.push(neighbor);
+                    }
+                }
+            }
+            index += 1;
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+
+    components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+    components
+}
+
+// BETWEENNESS CENTRALITY. Brandes' algorithm on an undirected graph: for each node, the number of shortest paths between other pairs that pass
+// through it. With samples below the number of nodes, only that many random sources are used and the result is scaled up to estimate the full sum.
+pub fn betweenness_centrality<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    samples: usize,
+    rng: &mut R,
+) -> HashMap<usize, f64> {
+    let (nodes, adjacency) = index_graph(graph);
+    let n = nodes.len();
+    let mut sources: Vec<usize> = (0..n).collect();
+    if samples < n {
+        sources.shuffle(rng);
+        sources.truncate(samples);
+    }
+
+    let mut centrality = vec![0.0; n];
+    let mut distance = vec![usize::MAX; n];
+    let mut paths = vec![0.0f64; n];
+    let mut dependency = vec![0.0; n];
+    let mut order = Vec::with_capacity(n);
+    for &source in &sources {
+        // BFS counting shortest paths, then dependencies accumulated in reverse BFS order
+        order.clear();
+        distance.iter_mut().for_each(|d| *d = usize::MAX);
+        paths.iter_mut().for_each(|p| *p = 0.0);
+        dependency.iter_mut().for_each(|d| *d = 0.0);
+        distance[source] = 0;
+        paths[source] = 1.0;
+        order.push(source);
+        let mut index = 0;
+        while index < order.len() {
+            let node = order[index];
+            for &neighbor in &adjacency[node] {
+                if distance[neighbor] == usize::MAX {
+                    distance[neighbor] = distance[node] + 1;
+                    order.push(neighbor);
+                }
+                if distance[neighbor] == distance[node] + 1 {
+                    paths[neighbor] += paths[node];
+                }
+            }
+            index += 1;
+        }
+        for &node in order.iter().rev() {
+            for &neighbor in &adjacency[node] {
+                if distance[neighbor] + 1 == distance[node] {
+                    dependency[neighbor] += paths[neighbor] / paths[node] * (1.0 + dependency[node]);
+                }
+            }
+            if node != source {
+                centrality[node] += dependency[node];
+            }
+        }
+    }
+
+    // Each undirected path is found from both of its ends
+    let scale = n as f64 / sources.len().max(1) as f64 / 2.0;
+    nodes.into_iter().zip(centrality.into_iter().map(|c| c * scale)).collect()
+}
@@ -0,0 +1,284 @@
+// Influence maximization: choose k seed accounts whose combined expected spread (diffusion.rs) is as large as possible.
+// - Greedy with CELF lazy evaluation (Leskovec et al.): spread is submodular, so a node's marginal gain can only shrink as seeds are added,
+//   and a node whose stale gain still tops the queue after re-evaluation is the true best. CELF++ (Goyal et al.) also records each node's
+//   gain assuming the current best is added, which saves another evaluation whenever that node is picked next.
+// - IMM (Tang, Shi and Xiao): reverse influence sampling. A random "reverse reachable" set holds the accounts that would have reached a random
+//   account in one random realization of the model; the seeds covering the most such sets have near-optimal spread, with a (1 - 1/e - epsilon)
+//   guarantee at the number of sets IMM works out.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::thread;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::diffusion::{run_seed, DiffusionGraph, DiffusionModel, Thresholds};
+
+// Queue entry for lazy greedy: a node with its last computed marginal gain and the CELF++ bookkeeping
+struct Candidate {
+    node: usize,
+    gain: f64,
+    // Seed set size when the gain was computed
+    round: usize,
+    // CELF++: the best node seen when the gain was computed, and the gain if that node were added first
+    previous_best: Option<usize>,
+    gain_after_best: f64,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    // Highest gain first, ties to the lowest index
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.gain.total_cmp(&other.gain).then(other.node.cmp(&self.node))
+    }
+}
+
+// GREEDY CELF. Choose k seeds among the candidate indices by lazy greedy hill climbing on Monte Carlo spread estimates (runs simulations each,
+// with the same random seed every time so estimates are compared on common random numbers). With plus_plus, uses CELF++.
+// Returns each chosen seed with the estimated spread of the seed set up to and including it.
+pub fn greedy_celf(
+    graph: &DiffusionGraph,
+    model: DiffusionModel,
+    candidates: &[usize],
+    k: usize,
+    runs: usize,
+    seed: u64,
+    plus_plus: bool,
+) -> Vec<(usize, f64)> {
+    let spread = |seeds: &[usize]| graph.expected_spread(model, seeds, runs, seed).mean;
+
+    // First pass: the spread of every candidate alone
+    let mut queue = BinaryHeap::new();
+    let mut current_best: Option<(usize, f64)> = None;
+    for &node in candidates {
+        let gain = spread(&[node]);
+        let (previous_best, gain_after_best) = match current_best {
+            Some((best, best_spread)) if plus_plus => (Some(best), spread(&[best, node]) - best_spread),
+            _ => (None, 0.0),
+        };
+        if current_best.is_none_or(|(_, best_gain)| gain > best_gain) {
+            current_best = Some((node, gain));
+        }
+        queue.push(Candidate { node, gain, round: 0, previous_best, gain_after_best });
+    }
+
+    let mut seeds: Vec<usize> = Vec::with_capacity(k);
+    let mut result = Vec::with_capacity(k);
+    let mut seeds_spread = 0.0;
+    let mut last_seed: Option<usize> = None;
+    // Best candidate re-evaluated in this round, with the spread of the seeds plus it
+    let mut round_best: Option<(usize, f64)> = None;
+
+    while seeds.len() < k {
+        let Some(mut candidate) = queue.pop() else {
+            break;
+        };
+
+        if candidate.round == seeds.len() {
+            // Up to date and still on top: no other node can do better
+            seeds.push(candidate.node);
+            seeds_spread += candidate.gain;
+            result.push((candidate.node, seeds_spread));
+            last_seed = Some(candidate.node);
+            round_best = None;
+            continue;
+        }
+
+        if plus_plus && candidate.round + 1 == seeds.len() && candidate.previous_best.is_some() && candidate.previous_best == last_seed {
+            // CELF++: the gain was already computed assuming the seed that was just added
+            candidate.gain = candidate.gain_after_best;
+        } else {
+            let mut with_node = seeds.clone();
+            with_node.push(candidate.node);
+            let spread_with_node = spread(&with_node);
+            candidate.gain = spread_with_node - seeds_spread;
+            if plus_plus {
+                match round_best {
+                    Some((best, best_spread)) => {
+                        with_node.push(best);
+                        candidate.previous_best = Some(best);
+                        candidate.gain_after_best = spread(&with_node) - best_spread;
+                    }
+                    None => candidate.previous_best = None,
+                }
+            }
+        }
+        candidate.round = seeds.len();
+
+        if round_best.is_none_or(|(_, best_spread)| seeds_spread + candidate.gain > best_spread) {
+            round_best = Some((candidate.node, seeds_spread + candidate.gain));
+        }
+        queue.push(candidate);
+    }
+
+    result
+}
+
+// One reverse reachable set: the accounts that reach a random root in a random realization of the model, found by walking the follow edges
+// backwards. Independent cascade keeps each incoming edge with its probability; linear threshold with random thresholds is equivalent to each
+// account listening to exactly one account it follows, chosen uniformly, so its set is a backwards random walk.
+fn reverse_reachable_set<R: Rng>(graph: &DiffusionGraph, model: DiffusionModel, visited: &mut [bool], rng: &mut R) -> Vec<usize> {
+    let root = rng.gen_range(0..graph.nodes.len());
+    let mut set = vec![root];
+    visited[root] = true;
+
+    match model {
+        DiffusionModel::IndependentCascade(probability) => {
+            let mut index = 0;
+            while index < set.len() {
+                let node = set[index];
+                let p = graph.edge_probability(probability, node);
+                for &influencer in &graph.following[node] {
+                    if !visited[influencer] && rng.gen::<f64>() < p {
+                        visited[influencer] = true;
+                        set.push(influencer);
+                    }
+                }
+                index += 1;
+            }
+        }
+        DiffusionModel::LinearThreshold(_) => {
+            let mut node = root;
+            while let Some(&influencer) = graph.following[node].get(rng.gen_range(0..graph.following[node].len().max(1))) {
+                if visited[influencer] {
+                    break;
+                }
+                visited[influencer] = true;
+                set.push(influencer);
+                node = influencer;
+            }
+        }
+    }
+
+    for &node in &set {
+        visited[node] = false;
+    }
+    set
+}
+
+// Add reverse reachable sets until there are `target` of them; set i is drawn with an rng seeded from (seed, i), in parallel
+fn extend_rr_sets(graph: &DiffusionGraph, model: DiffusionModel, rr_sets: &mut Vec<Vec<usize>>, target: usize, seed: u64) {
+    let start = rr_sets.len();
+    if target <= start {
+        return;
+    }
+    let threads = thread::available_parallelism().map_or(1, |n| n.get()).min(target - start);
+    let mut chunks: Vec<Vec<(usize, Vec<usize>)>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread_index| {
+                scope.spawn(move || {
+                    let mut visited = vec![false; graph.nodes.len()];
+                    (start + thread_index..target)
+                        .step_by(threads)
+                        .map(|index| {
+                            let mut rng = StdRng::seed_from_u64(run_seed(seed, index));
+                            (index, reverse_reachable_set(graph, model, &mut visited, &mut rng))
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut sets: Vec<(usize, Vec<usize>)> = chunks.iter_mut().flat_map(std::mem::take).collect();
+    sets.sort_unstable_by_key(|(index, _)| *index);
+    rr_sets.extend(sets.into_iter().map(|(_, set)| set));
+}
+
+// Greedy maximum coverage: the k nodes covering the most sets, and the fraction of sets they cover
+fn max_coverage(rr_sets: &[Vec<usize>], n: usize, k: usize) -> (Vec<usize>, f64) {
+    let mut sets_of_node: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (index, set) in rr_sets.iter().enumerate() {
+        for &node in set {
+            sets_of_node[node].push(index);
+        }
+    }
+    let mut coverage: Vec<usize> = sets_of_node.iter().map(Vec::len).collect();
+    let mut covered = vec![false; rr_sets.len()];
+    let mut seeds = Vec::with_capacity(k);
+    let mut total_covered = 0;
+
+    for _ in 0..k.min(n) {
+        let best = (0..n).max_by(|&a, &b| coverage[a].cmp(&coverage[b]).then(b.cmp(&a))).unwrap();
+        seeds.push(best);
+        for &set in &sets_of_node[best] {
+            if covered[set] {
+                continue;
+            }
+            covered[set] = true;
+            total_covered += 1;
+            for &node in &rr_sets[set] {
+                coverage[node] -= 1;
+            }
+        }
+    }
+
+    (seeds, total_covered as f64 / rr_sets.len().max(1) as f64)
+}
+
+fn log_binomial(n: usize, k: usize) -> f64 {
+    (0..k.min(n)).map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln()).sum()
+}
+
+// IMM. Choose k seeds by reverse influence sampling with accuracy epsilon, holding with probability at least 1 - 1/n.
+// Returns the seeds and the spread estimated from the reverse reachable sets, or None for linear threshold with fixed thresholds, which
+// has no reverse sampling equivalent.
+pub fn imm(graph: &DiffusionGraph, model: DiffusionModel, k: usize, epsilon: f64, seed: u64) -> Option<(Vec<usize>, f64)> {
+    if let DiffusionModel::LinearThreshold(Thresholds::Fixed(_)) = model {
+        return None;
+    }
+    let n = graph.nodes.len();
+    if n == 0 || k == 0 {
+        return Some((Vec::new(), 0.0));
+    }
+    let k = k.min(n);
+    let nf = n as f64;
+    let log_n = nf.ln().max(1e-9);
+    // l = 1, raised so the guarantee survives the two phases failing independently
+    let l = 1.0 + 2f64.ln() / log_n;
+    let log_choose = log_binomial(n, k);
+
+    // Sampling phase: find a lower bound on the optimal spread by guessing it halves at each step
+    let epsilon_prime = 2f64.sqrt() * epsilon;
+    let lambda_prime = (2.0 + 2.0 / 3.0 * epsilon_prime)
+        * (log_choose + l * log_n + nf.log2().max(1.0).ln())
+        * nf
+        / epsilon_prime.powi(2);
+    let mut rr_sets: Vec<Vec<usize>> = Vec::new();
+    let mut lower_bound = 1.0;
+    for i in 1..(nf.log2().ceil() as i32).max(2) {
+        let x = nf / 2f64.powi(i);
+        extend_rr_sets(graph, model, &mut rr_sets, (lambda_prime / x).ceil() as usize, seed);
+        let (_, fraction) = max_coverage(&rr_sets, n, k);
+        if nf * fraction >= (1.0 + epsilon_prime) * x {
+            lower_bound = nf * fraction / (1.0 + epsilon_prime);
+            break;
+        }
+    }
+
+    // Node selection phase: enough sets for the guarantee at that lower bound. They are drawn afresh with a different seed: reusing the
+    // sampling phase's sets makes them depend on the lower bound they were used to find, which breaks IMM's martingale argument (Chen 2018).
+    // seed + 1 is left to the influence command, which scores every seed set on it
+    let alpha = (l * log_n + 2f64.ln()).sqrt();
+    let beta = ((1.0 - 1.0 / std::f64::consts::E) * (log_choose + l * log_n + 2f64.ln())).sqrt();
+    let lambda_star = 2.0 * nf * ((1.0 - 1.0 / std::f64::consts::E) * alpha + beta).powi(2) / epsilon.powi(2);
+    let mut selection_sets: Vec<Vec<usize>> = Vec::new();
+    extend_rr_sets(graph, model, &mut selection_sets, (lambda_star / lower_bound).ceil() as usize, seed.wrapping_add(2));
+    let (seeds, fraction) = max_coverage(&selection_sets, n, k);
+
+    Some((seeds, nf * fraction))
+}
+
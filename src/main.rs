@@ -1,10 +1,54 @@
+use std::collections::{HashMap, HashSet};
 use std::io::{self};
 use rand::prelude::SliceRandom;
+mod analysis;
+mod attributes;
+mod cli;
+mod cliques;
+mod community;
+mod connectivity;
+mod degree;
+mod diffusion;
+mod directed;
+mod embedding;
+mod epidemic;
+mod evaluation;
+mod generators;
 mod graph;
+mod influence;
+mod motifs;
+mod nullmodel;
+mod paths;
+mod recommend;
+mod robustness;
+mod snap;
+mod spectral;
 
 const DATA_FILE: &str = "data/twitter_combined.txt";
 
+// Random walk settings for the ppr and rwr recommendation methods
+const RESTART_PROB: f64 = 0.15;
+const PPR_ITERATIONS: usize = 50;
+const RWR_WALKS: usize = 10_000;
+
+// Extra score for a "who to follow" candidate that already follows the user
+const FOLLOW_BACK_BOOST: f64 = 1.0;
+
+// Embedding settings for the similar recommendation method (p = q = 1 gives DeepWalk, otherwise node2vec)
+const EMBEDDINGS_FILE: &str = "data/embeddings.txt";
+const WALKS_PER_NODE: usize = 10;
+const WALK_LENGTH: usize = 40;
+const NODE2VEC_P: f64 = 1.0;
+const NODE2VEC_Q: f64 = 0.5;
+
 fn main() {
+    // COMMAND-LINE MODE. Arguments (e.g. `path 12 34`) run a single command instead of the interactive prompts
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        cli::run(&args);
+        return;
+    }
+
     // Step 1: RANDOMLY SAMPLE. Ask the user if they want to randomly sample the graph for quicker analysis
     let mut sample_size: Option<usize> = None;
 
@@ -29,8 +73,17 @@ fn main() {
         }
     }
 
-    // Step 3: READ THE GRAPH. Read the full graph
-    let graph = graph::read_graph(DATA_FILE);
+    // Step 3: READ THE GRAPH. Ask whether to keep follow direction, then read the full graph
+    println!("Would you like to load the graph as directed (follower -> followed account)? (yes/no)");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+    let directed = input.trim().to_lowercase() == "yes";
+
+    let graph = if directed {
+        graph::read_directed_graph(DATA_FILE)
+    } else {
+        graph::read_graph(DATA_FILE)
+    };
     let total_nodes = graph.len();
     println!("Total nodes in full graph: {}", total_nodes);
 
@@ -47,7 +100,9 @@ fn main() {
     // Step 5: GRAPH ANALYSIS. Analyze the graph
     let (num_nodes, num_edges, avg_degree, avg_sep) = graph::analyze_graph(&sampled_graph);
     println!("Sampled graph - Number of nodes: {}", num_nodes);
-    println!("Sampled graph - Number of edges: {}", num_edges / 2);
+    // An undirected edge is stored in both neighbor sets, a directed follow only in the follower's
+    let num_edges = if directed { num_edges } else { num_edges / 2 };
+    println!("Sampled graph - Number of edges: {}", num_edges);
     println!("Sampled graph - Average degree: {:.2}", avg_degree);
     println!("Sampled graph - Average degrees of separation: {:.2}", avg_sep);
 
@@ -64,7 +119,21 @@ fn main() {
         println!("Node ID: {} - Degree: {}", node, degree);
     }
 
-     // Step 9: PROFILE SUGGESTIONS. Ask user for a Node ID or select a random one
+     // Step 9: PROFILE SUGGESTIONS. Ask user how to rank recommendations, then for a Node ID or select a random one
+    // Directed graphs default to "who to follow", undirected graphs to shared neighbors
+    let default_method = if directed { "follow" } else { "shared" };
+    println!("Which recommendation method would you like to use? (shared = shared neighbors, ppr = personalized PageRank, rwr = random walk with restart, follow = who to follow on a directed graph, similar = most similar node2vec embeddings) [default: {}]", default_method);
+    let mut method = String::new();
+    io::stdin().read_line(&mut method).expect("Failed to read line");
+    let method = match method.trim().to_lowercase().as_str() {
+        "shared" => "shared",
+        "ppr" => "ppr",
+        "rwr" => "rwr",
+        "follow" => "follow",
+        "similar" => "similar",
+        _ => default_method,
+    };
+
     println!("Would you like to provide a Node ID for other recommended profiles to follow? (yes/no)");
     let mut input = String::new();
     io::stdin().read_line(&mut input).expect("Failed to read line");
@@ -86,16 +155,20 @@ fn main() {
                 // If the user input is valid, proceed with recommendations
                 if sampled_graph.contains_key(&id) {
                     // If the node exists, proceed with recommendations
-                    let suggestions = graph::most_shared_neighbors(&sampled_graph, id);
+                    if method == "shared" {
+                        let suggestions = graph::most_shared_neighbors(&sampled_graph, id);
 
-                    // TOP 5 PROFILES TO FOLLOW. Display the top 5 recommendations
-                    if suggestions.is_empty() {
-                        println!("Node {} has no shared neighbors in the sampled graph.", id);
-                    } else {
-                        println!("Top 5 Recommended Profiles for Node {}:", id);
-                        for (neighbor, shared_count) in suggestions {
-                            println!("Node {}: {} shared neighbors", neighbor, shared_count);
+                        // TOP 5 PROFILES TO FOLLOW. Display the top 5 recommendations
+                        if suggestions.is_empty() {
+                            println!("Node {} has no shared neighbors in the sampled graph.", id);
+                        } else {
+                            println!("Top 5 Recommended Profiles for Node {}:", id);
+                            for (neighbor, shared_count) in suggestions {
+                                println!("Node {}: {} shared neighbors", neighbor, shared_count);
+                            }
                         }
+                    } else {
+                        print_recommendations(&sampled_graph, id, method);
                     }
                     valid_node_found = true;  // Valid input, stop asking
                 } else {
@@ -121,14 +194,8 @@ fn main() {
 
             println!("Randomly selected Node ID for recommendations: {}", random_node_id);
 
-            // PROFILE RECOMMENDATIONS ON SHARED NEIGHBORS (if three profiles I follow all follow the same account, suggest that I follow that account as well). Get recommendations for the random node
-            let suggestions = graph::most_shared_neighbors(&sampled_graph, random_node_id);
-    
-            // TOP 5. Display the top 5 recommended profiles (nodes with most shared neighbors)
-            println!("Top 5 Recommended Profiles for Node ID {}:", random_node_id);
-            for (id, shared_neighbors) in suggestions.iter().take(5) {
-                println!("Node ID: {} - Shared Neighbors: {}", id, shared_neighbors);
-            }
+            // PROFILE RECOMMENDATIONS. Get recommendations for the random node
+            print_recommendations(&sampled_graph, random_node_id, method);
         }
     } else if input == "no" {
         // NO NODE ID. Handle the case where the user doesn't want to provide a Node ID
@@ -141,17 +208,85 @@ fn main() {
         println!("Randomly selected Node ID for recommendations: {}", random_node_id);
 
         // Get recommendations for the random node
-        let suggestions = graph::most_shared_neighbors(&sampled_graph, random_node_id);
-        
-        // Display the top 5 recommended profiles (nodes with most shared neighbors)
-        println!("Top 5 Recommended Profiles for Node ID {}:", random_node_id);
+        print_recommendations(&sampled_graph, random_node_id, method);
+    } else {
+        println!("Invalid input. Please enter 'yes' or 'no'.");
+    }
+}
+
+// TOP 5 PROFILES TO FOLLOW. Display the top 5 recommendations for a node using the chosen method
+fn print_recommendations(
+    graph: &HashMap<usize, HashSet<usize>>,
+    node_id: usize,
+    method: &str,
+) {
+    if method == "shared" {
+        // PROFILE RECOMMENDATIONS ON SHARED NEIGHBORS (if three profiles I follow all follow the same account, suggest that I follow that account as well)
+        let suggestions = graph::most_shared_neighbors(graph, node_id);
+        if suggestions.is_empty() {
+            println!("Node {} has no shared neighbors in the sampled graph.", node_id);
+            return;
+        }
+        println!("Top 5 Recommended Profiles for Node ID {}:", node_id);
         for (id, shared_neighbors) in suggestions.iter().take(5) {
             println!("Node ID: {} - Shared Neighbors: {}", id, shared_neighbors);
         }
+        return;
+    }
+
+    if method == "follow" {
+        // WHO TO FOLLOW. Accounts followed by the accounts this node follows, boosted if they already follow the node
+        let suggestions = recommend::who_to_follow(graph, node_id, FOLLOW_BACK_BOOST);
+        if suggestions.is_empty() {
+            println!("Node {} has no followees-of-followees to recommend in the sampled graph.", node_id);
+            return;
+        }
+        println!("Top 5 Recommended Profiles for Node ID {}:", node_id);
+        for (id, score) in suggestions {
+            println!("Node ID: {} - Score: {:.1}", id, score);
+        }
+        return;
+    }
+
+    if method == "similar" {
+        // MOST SIMILAR ACCOUNTS. Train node2vec embeddings on the graph, save them, and recommend the nearest accounts not already followed
+        println!("Training node embeddings (this may take a while on a large graph) . . .");
+        let mut rng = rand::thread_rng();
+        let (nodes, adjacency) = graph::index_graph(graph);
+        let walks = embedding::node2vec_walks(&adjacency, WALKS_PER_NODE, WALK_LENGTH, NODE2VEC_P, NODE2VEC_Q, &mut rng);
+        let embeddings = embedding::train_skipgram(&walks, nodes.len(), &embedding::SkipGramSettings::default(), &mut rng);
+        match embedding::save_embeddings(EMBEDDINGS_FILE, &nodes, &embeddings) {
+            Ok(()) => println!("Saved embeddings to {}", EMBEDDINGS_FILE),
+            Err(e) => println!("Could not save embeddings to {}: {}", EMBEDDINGS_FILE, e),
+        }
+
+        let binding = HashSet::new();
+        let already_followed = graph.get(&node_id).unwrap_or(&binding);
+        let suggestions = embedding::most_similar(&nodes, &embeddings, node_id, already_followed, 5);
+        println!("Top 5 Recommended Profiles for Node ID {}:", node_id);
+        for (id, similarity) in suggestions {
+            println!("Node ID: {} - Similarity: {:.4}", id, similarity);
+        }
+        return;
+    }
+
+    // RANDOM WALK RECOMMENDATIONS. Score accounts by how often a walk that keeps restarting at the node visits them, which reaches past two hops
+    let scores = if method == "ppr" {
+        recommend::personalized_pagerank(graph, node_id, RESTART_PROB, PPR_ITERATIONS)
     } else {
-        println!("Invalid input. Please enter 'yes' or 'no'.");
+        recommend::random_walk_with_restart(graph, node_id, RESTART_PROB, RWR_WALKS, &mut rand::thread_rng())
+    };
+    let suggestions = recommend::top_walk_recommendations(graph, node_id, &scores);
+    if suggestions.is_empty() {
+        println!("Node {} has no reachable profiles to recommend in the sampled graph.", node_id);
+        return;
+    }
+    println!("Top 5 Recommended Profiles for Node ID {}:", node_id);
+    for (id, score) in suggestions {
+        println!("Node ID: {} - Score: {:.6}", id, score);
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests;
\ No newline at end of file
@@ -0,0 +1,231 @@
+// Network motifs: small connected patterns of 3 or 4 accounts that occur far more (or less) often than in random graphs with the same degrees.
+// Subgraphs are enumerated with Wernicke's ESU algorithm, which visits every connected node set exactly once, and each set is labelled by the
+// canonical code of its isomorphism class. Significance is a z-score against an ensemble of degree-preserving randomizations from nullmodel.rs.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::thread;
+use rand::Rng;
+use crate::graph::index_graph;
+use crate::nullmodel::randomized_copy;
+
+// Count and significance of one motif class
+pub struct MotifScore {
+    pub code: usize,
+    pub count: u64,
+    pub random_mean: f64,
+    pub random_std: f64,
+    pub z_score: f64,
+}
+
+// Bit for the arc (or, undirected, the pair) from position i to position j of a size-k subgraph
+fn edge_bit(i: usize, j: usize, size: usize, directed: bool) -> usize {
+    if directed {
+        i * size + j
+    } else {
+        let (i, j) = (i.min(j), i.max(j));
+        i * size + j
+    }
+}
+
+// Every ordering of 0..size
+fn permutations(size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for smaller in permutations(size - 1) {
+        for position in 0..size {
+            let mut permutation = smaller.clone();
+            permutation.insert(position, size - 1);
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+// Canonical code (the smallest code over all relabellings) of every raw adjacency code of a size-k subgraph
+fn canonical_table(size: usize, directed: bool) -> Vec<usize> {
+    let bits = size * size;
+    let permutations = permutations(size);
+    let pairs: Vec<(usize, usize)> = (0..size)
+        .flat_map(|i| (0..size).map(move |j| (i, j)))
+        .filter(|&(i, j)| if directed { i != j } else { i < j })
+        .collect();
+
+    (0..1usize << bits)
+        .map(|code| {
+            // Codes that set bits outside the valid pairs never occur
+            if pairs.iter().map(|&(i, j)| 1 << edge_bit(i, j, size, directed)).sum::<usize>() & code != code {
+                return code;
+            }
+            permutations
+                .iter()
+                .map(|permutation| {
+                    pairs
+                        .iter()
+                        .filter(|&&(i, j)| code & (1 << edge_bit(i, j, size, directed)) != 0)
+                        .map(|&(i, j)| 1 << edge_bit(permutation[i], permutation[j], size, directed))
+                        .sum::<usize>()
+                })
+                .min()
+                .unwrap()
+        })
+        .collect()
+}
+
+// ESU state shared by every start node handled by one thread
+struct Enumerator<'a> {
+    out: &'a [Vec<usize>],
+    neighbors: &'a [Vec<usize>],
+    size: usize,
+    directed: bool,
+    canonical: &'a [usize],
+    counts: HashMap<usize, u64>,
+}
+
+impl Enumerator<'_> {
+    // Extend the current subgraph with each node of the extension set in turn, adding that node's exclusive neighbors above the start node
+    fn extend(&mut self, subgraph: &mut Vec<usize>, extension: &[usize], start: usize) {
+        if subgraph.len() == self.size {
+            let code = self.code(subgraph);
+            *self.counts.entry(self.canonical[code]).or_insert(0) += 1;
+            return;
+        }
+
+        for (index, &w) in extension.iter().enumerate() {
+            let mut next: Vec<usize> = extension[index + 1..].to_vec();
+            for &u in &self.neighbors[w] {
+                if u > start
+                    && !subgraph.contains(&u)
+                    && !next.contains(&u)
+                    && subgraph.iter().all(|&s| self.neighbors[s].binary_search(&u).is_err())
+                {
+                    next.push(u);
+                }
+            }
+            subgraph.push(w);
+            self.extend(subgraph, &next, start);
+            subgraph.pop();
+        }
+    }
+
+    fn code(&self, subgraph: &[usize]) -> usize {
+        let mut code = 0;
+        for (i, &a) in subgraph.iter().enumerate() {
+            for (j, &b) in subgraph.iter().enumerate() {
+                if i != j && (self.directed || i < j) && self.linked(a, b) {
+                    code |= 1 << edge_bit(i, j, self.size, self.directed);
+                }
+            }
+        }
+        code
+    }
+
+    fn linked(&self, a: usize, b: usize) -> bool {
+        if self.directed {
+            self.out[a].binary_search(&b).is_ok()
+        } else {
+            self.neighbors[a].binary_search(&b).is_ok()
+        }
+    }
+}
+
+// COUNT MOTIFS. Number of connected induced subgraphs of 3 or 4 nodes in each isomorphism class, keyed by canonical code.
+// With directed set, arcs are distinguished by direction (graph maps each follower to the accounts they follow); otherwise the graph is
+// treated as undirected. Start nodes are split across all available threads.
+#[allow(dead_code)]
+pub fn count_motifs(graph: &HashMap<usize, HashSet<usize>>, size: usize, directed: bool) -> BTreeMap<usize, u64> {
+    count_with_table(graph, size, directed, &canonical_table(size, directed))
+}
+
+// Motif counts with an already built canonical table, so motif_significance builds it once for every randomized copy
+fn count_with_table(graph: &HashMap<usize, HashSet<usize>>, size: usize, directed: bool, canonical: &[usize]) -> BTreeMap<usize, u64> {
+    let (_, out) = index_graph(graph);
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); out.len()];
+    for (v, targets) in out.iter().enumerate() {
+        for &u in targets.iter().filter(|&&u| u != v) {
+            neighbors[v].push(u);
+            neighbors[u].push(v);
+        }
+    }
+    for list in &mut neighbors {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let partial_counts: Vec<HashMap<usize, u64>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread_index| {
+                let (out, neighbors) = (&out, &neighbors);
+                scope.spawn(move || {
+                    let mut enumerator = Enumerator { out, neighbors, size, directed, canonical, counts: HashMap::new() };
+                    // Interleaving start nodes spreads the hubs across threads
+                    for start in (thread_index..neighbors.len()).step_by(threads) {
+                        let extension: Vec<usize> = neighbors[start].iter().cloned().filter(|&u| u > start).collect();
+                        enumerator.extend(&mut vec![start], &extension, start);
+                    }
+                    enumerator.counts
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut counts = BTreeMap::new();
+    for partial in partial_counts {
+        for (code, count) in partial {
+            *counts.entry(code).or_insert(0) += count;
+        }
+    }
+    counts
+}
+
+// DESCRIBE MOTIF. The edges of a motif's canonical form, e.g. "0->1 1->2" (directed) or "0-1 1-2" (undirected)
+pub fn describe_motif(code: usize, size: usize, directed: bool) -> String {
+    let mut edges = Vec::new();
+    for i in 0..size {
+        for j in 0..size {
+            if i != j && (directed || i < j) && code & (1 << edge_bit(i, j, size, directed)) != 0 {
+                edges.push(format!("{}{}{}", i, if directed { "->" } else { "-" }, j));
+            }
+        }
+    }
+    edges.join(" ")
+}
+
+// MOTIF SIGNIFICANCE. Motif counts in the graph compared with `randomizations` degree-preserving randomizations of it (swaps_per_edge edge swaps
+// per edge each, keeping in- and out-degrees when directed). z = (count - random mean) / random standard deviation, and 0 when the ensemble
+// never varies. Classes seen only in the randomized graphs are reported with a count of 0.
+pub fn motif_significance<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    size: usize,
+    directed: bool,
+    randomizations: usize,
+    swaps_per_edge: usize,
+    rng: &mut R,
+) -> Vec<MotifScore> {
+    let canonical = canonical_table(size, directed);
+    let observed = count_with_table(graph, size, directed, &canonical);
+    let mut samples: BTreeMap<usize, Vec<u64>> = observed.keys().map(|&code| (code, Vec::new())).collect();
+    for _ in 0..randomizations {
+        let randomized = randomized_copy(graph, directed, swaps_per_edge, rng);
+        for (code, count) in count_with_table(&randomized, size, directed, &canonical) {
+            samples.entry(code).or_default().push(count);
+        }
+    }
+
+    samples
+        .into_iter()
+        .map(|(code, mut counts)| {
+            // Randomizations where the class never appeared count as zeros
+            counts.resize(randomizations, 0);
+            let n = randomizations.max(1) as f64;
+            let random_mean = counts.iter().sum::<u64>() as f64 / n;
+            let random_std = (counts.iter().map(|&c| (c as f64 - random_mean).powi(2)).sum::<f64>() / n).sqrt();
+            let count = observed.get(&code).copied().unwrap_or(0);
+            let z_score = if random_std > 0.0 { (count as f64 - random_mean) / random_std } else { 0.0 };
+            MotifScore { code, count, random_mean, random_std, z_score }
+        })
+        .collect()
+}
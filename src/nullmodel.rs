@@ -0,0 +1,118 @@
+// Degree-preserving null models. Randomizing a graph while keeping every account's degree separates structure that the degree sequence alone
+// explains (hubs meeting hubs simply because they have many edges) from structure that needs another explanation.
+
+use std::collections::{HashMap, HashSet};
+use rand::Rng;
+
+// DOUBLE EDGE SWAP. Randomize an undirected graph in place by repeatedly replacing edges a-b and c-d with a-d and c-b, skipping swaps
+// that would create a self-loop or a duplicate edge. Attempts up to max_tries swaps and returns how many succeeded.
+pub fn double_edge_swap<R: Rng>(
+    graph: &mut HashMap<usize, HashSet<usize>>,
+    swaps: usize,
+    max_tries: usize,
+    rng: &mut R,
+) -> usize {
+    let mut edges: Vec<(usize, usize)> = graph
+        .iter()
+        .flat_map(|(&a, neighbors)| neighbors.iter().filter(move |&&b| a < b).map(move |&b| (a, b)))
+        .collect();
+    // Sorting keeps the result reproducible for a given seed regardless of hash map order
+    edges.sort_unstable();
+    if edges.len() < 2 {
+        return 0;
+    }
+
+    let mut done = 0;
+    for _ in 0..max_tries {
+        if done == swaps {
+            break;
+        }
+        let first = rng.gen_range(0..edges.len());
+        let second = rng.gen_range(0..edges.len());
+        if first == second {
+            continue;
+        }
+        let (a, b) = edges[first];
+        // Either orientation of the second edge gives a different swap
+        let (c, d) = if rng.gen::<bool>() { edges[second] } else { (edges[second].1, edges[second].0) };
+        if a == d || c == b || graph[&a].contains(&d) || graph[&c].contains(&b) {
+            continue;
+        }
+
+        for (x, y) in [(a, b), (c, d)] {
+            graph.get_mut(&x).unwrap().remove(&y);
+            graph.get_mut(&y).unwrap().remove(&x);
+        }
+        for (x, y) in [(a, d), (c, b)] {
+            graph.get_mut(&x).unwrap().insert(y);
+            graph.get_mut(&y).unwrap().insert(x);
+        }
+        edges[first] = (a.min(d), a.max(d));
+        edges[second] = (c.min(b), c.max(b));
+        done += 1;
+    }
+
+    done
+}
+
+// DIRECTED EDGE SWAP. Randomize a directed graph in place by replacing arcs a -> b and c -> d with a -> d and c -> b, which keeps every
+// in- and out-degree. Swaps that would create a self-loop or an arc that already exists are skipped. Returns how many succeeded.
+pub fn directed_edge_swap<R: Rng>(
+    graph: &mut HashMap<usize, HashSet<usize>>,
+    swaps: usize,
+    max_tries: usize,
+    rng: &mut R,
+) -> usize {
+    let mut edges: Vec<(usize, usize)> = graph
+        .iter()
+        .flat_map(|(&a, targets)| targets.iter().map(move |&b| (a, b)))
+        .collect();
+    edges.sort_unstable();
+    if edges.len() < 2 {
+        return 0;
+    }
+
+    let mut done = 0;
+    for _ in 0..max_tries {
+        if done == swaps {
+            break;
+        }
+        let first = rng.gen_range(0..edges.len());
+        let second = rng.gen_range(0..edges.len());
+        let ((a, b), (c, d)) = (edges[first], edges[second]);
+        if a == c || b == d || a == d || c == b || graph[&a].contains(&d) || graph[&c].contains(&b) {
+            continue;
+        }
+
+        let sources = graph.get_mut(&a).unwrap();
+        sources.remove(&b);
+        sources.insert(d);
+        let sources = graph.get_mut(&c).unwrap();
+        sources.remove(&d);
+        sources.insert(b);
+        edges[first] = (a, d);
+        edges[second] = (c, b);
+        done += 1;
+    }
+
+    done
+}
+
+// MASLOV–SNEPPEN RANDOMIZATION. A randomized copy of the graph with swaps_per_edge successful swaps per edge (allowing up to ten tries per
+// swap): directed edge swaps keep every in- and out-degree, undirected double edge swaps keep every degree
+pub fn randomized_copy<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    directed: bool,
+    swaps_per_edge: usize,
+    rng: &mut R,
+) -> HashMap<usize, HashSet<usize>> {
+    let mut randomized = graph.clone();
+    let arcs: usize = graph.values().map(HashSet::len).sum();
+    if directed {
+        directed_edge_swap(&mut randomized, swaps_per_edge * arcs, 10 * swaps_per_edge * arcs, rng);
+    } else {
+        let edges = arcs / 2;
+        double_edge_swap(&mut randomized, swaps_per_edge * edges, 10 * swaps_per_edge * edges, rng);
+    }
+    randomized
+}
@@ -0,0 +1,261 @@
+// Point-to-point distance queries. compute_avg_degrees_of_separation in graph.rs only summarizes distances from one node; these functions answer "how is user A connected to user B" with the actual chain of accounts.
+// All functions assume an undirected graph as returned by read_graph, so the backward search can walk the same neighbor sets.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::graph::{connected_components, index_graph};
+
+// Eccentricity extremes of one connected component
+pub struct EccentricitySummary {
+    pub size: usize,
+    pub radius: usize,
+    pub diameter: usize,
+    // Nodes with eccentricity equal to the radius, and equal to the diameter (both sorted)
+    pub center: Vec<usize>,
+    pub periphery: Vec<usize>,
+}
+
+// SHORTEST PATH. Bidirectional BFS from both ends, returning the node IDs along one shortest path (None if the nodes are not connected)
+pub fn shortest_path(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source: usize,
+    target: usize,
+) -> Option<Vec<usize>> {
+    if !graph.contains_key(&source) || !graph.contains_key(&target) {
+        return None;
+    }
+    if source == target {
+        return Some(vec![source]);
+    }
+
+    // Each side maps a visited node to (parent, distance from that side's start)
+    let mut forward: HashMap<usize, (usize, usize)> = HashMap::from([(source, (source, 0))]);
+    let mut backward: HashMap<usize, (usize, usize)> = HashMap::from([(target, (target, 0))]);
+    let mut forward_frontier = vec![source];
+    let mut backward_frontier = vec![target];
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        // Expand whichever side has the smaller frontier
+        let expand_forward = forward_frontier.len() <= backward_frontier.len();
+        let (frontier, visited, other) = if expand_forward {
+            (&mut forward_frontier, &mut forward, &backward)
+        } else {
+            (&mut backward_frontier, &mut backward, &forward)
+        };
+
+        // Finish the whole level before stopping so the shortest meeting point is chosen
+        let mut best: Option<(usize, usize, usize)> = None;
+        let mut next_frontier = Vec::new();
+        for &current in frontier.iter() {
+            let distance = visited[&current].1;
+            for &neighbor in graph.get(&current).into_iter().flatten() {
+                if let Some(&(_, other_distance)) = other.get(&neighbor) {
+                    let total = distance + 1 + other_distance;
+                    if best.is_none_or(|(length, _, _)| total < length) {
+                        best = Some((total, current, neighbor));
+                    }
+                }
+                if let Entry::Vacant(entry) = visited.entry(neighbor) {
+                    entry.insert((current, distance + 1));
+                    next_frontier.push(neighbor);
+                }
+            }
+        }
+        *frontier = next_frontier;
+
+        if let Some((_, near, far)) = best {
+            // near is on the expanded side, far on the other side
+            let (forward_end, backward_start) = if expand_forward { (near, far) } else { (far, near) };
+            let mut path = trace_back(&forward, forward_end);
+            path.reverse();
+            path.extend(trace_back(&backward, backward_start));
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+// Follow parent links from a node back to the start of its search
+fn trace_back(parents: &HashMap<usize, (usize, usize)>, mut node: usize) -> Vec<usize> {
+    let mut chain = vec![node];
+    while parents[&node].0 != node {
+        node = parents[&node].0;
+        chain.push(node);
+    }
+    chain
+}
+
+// Breadth-first search from the source that stops after the target's level, recording each node's distance and its predecessors on shortest paths
+fn shortest_path_dag(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source: usize,
+    target: usize,
+) -> HashMap<usize, (usize, Vec<usize>)> {
+    let mut levels: HashMap<usize, (usize, Vec<usize>)> = HashMap::from([(source, (0, Vec::new()))]);
+    let mut queue = VecDeque::from([source]);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = levels[&current].0;
+        if levels.get(&target).is_some_and(|&(target_distance, _)| distance >= target_distance) {
+            break;
+        }
+        for &neighbor in graph.get(&current).into_iter().flatten() {
+            match levels.get_mut(&neighbor) {
+                None => {
+                    levels.insert(neighbor, (distance + 1, vec![current]));
+                    queue.push_back(neighbor);
+                }
+                Some((neighbor_distance, predecessors)) if *neighbor_distance == distance + 1 => {
+                    predecessors.push(current);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    levels
+}
+
+// ALL SHORTEST PATHS. Every shortest path between the two nodes. The number of paths can grow exponentially with distance, so use count_shortest_paths for just the total
+pub fn all_shortest_paths(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source: usize,
+    target: usize,
+) -> Vec<Vec<usize>> {
+    if !graph.contains_key(&source) || !graph.contains_key(&target) {
+        return Vec::new();
+    }
+    let levels = shortest_path_dag(graph, source, target);
+    if !levels.contains_key(&target) {
+        return Vec::new();
+    }
+
+    // Walk the predecessor lists back from the target, depth first
+    let mut paths = Vec::new();
+    let mut stack = vec![vec![target]];
+    while let Some(partial) = stack.pop() {
+        let last = *partial.last().unwrap();
+        if last == source {
+            paths.push(partial.into_iter().rev().collect());
+            continue;
+        }
+        for &predecessor in &levels[&last].1 {
+            let mut extended = partial.clone();
+            extended.push(predecessor);
+            stack.push(extended);
+        }
+    }
+
+    paths.sort();
+    paths
+}
+
+// COUNT SHORTEST PATHS. Number of distinct shortest paths between the two nodes, without listing them
+pub fn count_shortest_paths(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source: usize,
+    target: usize,
+) -> u128 {
+    if !graph.contains_key(&source) || !graph.contains_key(&target) {
+        return 0;
+    }
+    let levels = shortest_path_dag(graph, source, target);
+    let Some(&(target_distance, _)) = levels.get(&target) else {
+        return 0;
+    };
+
+    // Paths into a node = sum of paths into its predecessors, processed in order of distance
+    let mut by_distance: Vec<(&usize, &(usize, Vec<usize>))> = levels
+        .iter()
+        .filter(|(_, (distance, _))| *distance <= target_distance)
+        .collect();
+    by_distance.sort_by_key(|(_, (distance, _))| *distance);
+
+    let mut counts: HashMap<usize, u128> = HashMap::from([(source, 1)]);
+    for (&node, (_, predecessors)) in by_distance {
+        if node != source {
+            let count = predecessors.iter().map(|p| counts.get(p).copied().unwrap_or(0)).sum();
+            counts.insert(node, count);
+        }
+    }
+
+    counts[&target]
+}
+
+// Distances from the source to every node, by index (usize::MAX where unreachable)
+fn bfs_distances(adjacency: &[Vec<usize>], source: usize, distances: &mut [usize]) {
+    distances.iter_mut().for_each(|d| *d = usize::MAX);
+    distances[source] = 0;
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        for &neighbor in &adjacency[node] {
+            if distances[neighbor] == usize::MAX {
+                distances[neighbor] = distances[node] + 1;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+}
+
+// ECCENTRICITIES. Each node's greatest distance to any node of its own connected component, by Takes and Kosters' bounding eccentricities:
+// a BFS from v gives every w the bounds max(d(v,w), ecc(v) - d(v,w)) <= ecc(w) <= ecc(v) + d(v,w), and nodes whose bounds meet need no BFS
+// of their own. Alternating between the candidate with the smallest lower bound and the one with the largest upper bound (highest degree
+// first among ties) usually settles a large sparse graph in a small number of searches.
+pub fn eccentricities(graph: &HashMap<usize, HashSet<usize>>) -> HashMap<usize, usize> {
+    let (nodes, adjacency) = index_graph(graph);
+    let n = nodes.len();
+    let mut lower = vec![0; n];
+    let mut upper = vec![usize::MAX; n];
+    let mut distances = vec![usize::MAX; n];
+    let mut candidates: Vec<usize> = (0..n).collect();
+    let mut pick_lower = true;
+
+    while !candidates.is_empty() {
+        let &source = if pick_lower {
+            candidates.iter().min_by(|&&a, &&b| lower[a].cmp(&lower[b]).then(adjacency[b].len().cmp(&adjacency[a].len())))
+        } else {
+            candidates.iter().max_by(|&&a, &&b| upper[a].cmp(&upper[b]).then(adjacency[a].len().cmp(&adjacency[b].len())))
+        }
+        .unwrap();
+        pick_lower = !pick_lower;
+
+        bfs_distances(&adjacency, source, &mut distances);
+        let eccentricity = distances.iter().filter(|&&d| d != usize::MAX).max().copied().unwrap_or(0);
+        lower[source] = eccentricity;
+        upper[source] = eccentricity;
+
+        // Only nodes in the source's component learn anything from this search
+        for &node in &candidates {
+            let distance = distances[node];
+            if distance == usize::MAX {
+                continue;
+            }
+            lower[node] = lower[node].max(distance).max(eccentricity - distance);
+            upper[node] = upper[node].min(eccentricity + distance);
+        }
+        candidates.retain(|&node| lower[node] != upper[node]);
+    }
+
+    nodes.into_iter().zip(lower).collect()
+}
+
+// ECCENTRICITY SUMMARY. Radius, diameter, center and periphery of every connected component with at least two nodes, largest component first
+pub fn eccentricity_summary(graph: &HashMap<usize, HashSet<usize>>) -> Vec<EccentricitySummary> {
+    let eccentricity = eccentricities(graph);
+    connected_components(graph)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| {
+            let radius = component.iter().map(|node| eccentricity[node]).min().unwrap_or(0);
+            let diameter = component.iter().map(|node| eccentricity[node]).max().unwrap_or(0);
+            EccentricitySummary {
+                size: component.len(),
+                radius,
+                diameter,
+                center: component.iter().cloned().filter(|node| eccentricity[node] == radius).collect(),
+                periphery: component.iter().cloned().filter(|node| eccentricity[node] == diameter).collect(),
+            }
+        })
+        .collect()
+}
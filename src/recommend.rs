@@ -0,0 +1,157 @@
+// Recommendations that look past a user's immediate neighborhood. Counting shared neighbors (most_shared_neighbors in graph.rs) can only suggest accounts exactly two hops away.
+// A random walk that keeps jumping back to the target user instead scores every account it can reach, so well-connected accounts three or more hops away can be recommended too.
+
+use std::collections::{HashMap, HashSet};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::graph::index_graph;
+
+// PERSONALIZED PAGERANK. Power iteration for the stationary distribution of a walk that restarts at the source with probability restart_prob at each step
+pub fn personalized_pagerank(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source: usize,
+    restart_prob: f64,
+    iterations: usize,
+) -> HashMap<usize, f64> {
+    let (nodes, adjacency) = index_graph(graph);
+    let source_index = match nodes.binary_search(&source) {
+        Ok(index) => index,
+        Err(_) => return HashMap::new(),
+    };
+
+    let mut scores = vec![0.0; nodes.len()];
+    scores[source_index] = 1.0;
+
+    for _ in 0..iterations {
+        let mut next = vec![0.0; nodes.len()];
+        let mut dangling = 0.0;
+        for (node, neighbors) in adjacency.iter().enumerate() {
+            if scores[node] == 0.0 {
+                continue;
+            }
+            if neighbors.is_empty() {
+                // A walk with nowhere to go restarts at the source
+                dangling += scores[node];
+                continue;
+            }
+            let share = (1.0 - restart_prob) * scores[node] / neighbors.len() as f64;
+            for &neighbor in neighbors {
+                next[neighbor] += share;
+            }
+        }
+        next[source_index] += restart_prob + (1.0 - restart_prob) * dangling;
+
+        // Stop early once the scores have settled
+        let change: f64 = scores.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < 1e-10 {
+            break;
+        }
+    }
+
+    nodes
+        .into_iter()
+        .zip(scores)
+        .filter(|&(_, score)| score > 0.0)
+        .collect()
+}
+
+// RANDOM WALK WITH RESTART. Monte Carlo estimate of personalized PageRank: the fraction of all steps, over num_walks walks from the source, spent at each node
+pub fn random_walk_with_restart<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    source: usize,
+    restart_prob: f64,
+    num_walks: usize,
+    rng: &mut R,
+) -> HashMap<usize, f64> {
+    let (nodes, adjacency) = index_graph(graph);
+    let source_index = match nodes.binary_search(&source) {
+        Ok(index) => index,
+        Err(_) => return HashMap::new(),
+    };
+
+    let mut visits = vec![0usize; nodes.len()];
+    let mut total_steps = 0;
+
+    for _ in 0..num_walks {
+        let mut current = source_index;
+        loop {
+            visits[current] += 1;
+            total_steps += 1;
+            if rng.gen::<f64>() < restart_prob {
+                break;
+            }
+            match adjacency[current].choose(rng) {
+                Some(&next) => current = next,
+                None => break,
+            }
+        }
+    }
+
+    nodes
+        .into_iter()
+        .zip(visits)
+        .filter(|&(_, count)| count > 0)
+        .map(|(node, count)| (node, count as f64 / total_steps as f64))
+        .collect()
+}
+
+// TOP 5 WALK RECOMMENDATIONS. Rank walk scores, skipping the selected node and the accounts it is already connected to
+pub fn top_walk_recommendations(
+    graph: &HashMap<usize, HashSet<usize>>,
+    selected_node: usize,
+    scores: &HashMap<usize, f64>,
+) -> Vec<(usize, f64)> {
+    let binding = HashSet::new();
+    let selected_neighbors = graph
+        .get(&selected_node)
+        .unwrap_or(&binding);
+
+    let mut ranked: Vec<(usize, f64)> = scores
+        .iter()
+        .filter(|(node, _)| **node != selected_node && !selected_neighbors.contains(node))
+        .map(|(&node, &score)| (node, score))
+        .collect();
+
+    // Highest score first, ties broken by node ID so the output is stable
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(5);
+
+    ranked
+}
+
+// WHO TO FOLLOW. Suggest accounts followed by the accounts the selected node follows, scored by how many of its followees follow them, plus follow_back_boost if the account already follows the selected node. Expects a directed graph from read_directed_graph.
+pub fn who_to_follow(
+    graph: &HashMap<usize, HashSet<usize>>,
+    selected_node: usize,
+    follow_back_boost: f64,
+) -> Vec<(usize, f64)> {
+    let binding = HashSet::new();
+    let followees = graph
+        .get(&selected_node)
+        .unwrap_or(&binding);
+
+    let mut scores: HashMap<usize, f64> = HashMap::new();
+    for followee in followees {
+        if let Some(candidates) = graph.get(followee) {
+            for &candidate in candidates {
+                if candidate != selected_node && !followees.contains(&candidate) {
+                    *scores.entry(candidate).or_insert(0.0) += 1.0;
+                }
+            }
+        }
+    }
+
+    // Accounts that already follow the selected node are more likely to be followed back
+    for (candidate, score) in scores.iter_mut() {
+        if graph.get(candidate).is_some_and(|n| n.contains(&selected_node)) {
+            *score += follow_back_boost;
+        }
+    }
+
+    let mut ranked: Vec<(usize, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(5);
+
+    ranked
+}
@@ -0,0 +1,107 @@
+// Loaders for the per-ego files of the SNAP Twitter dataset. twitter_combined.txt merges every ego network, but the dataset also ships each ego network separately as <ego>.edges alongside <ego>.circles and the feature files.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use crate::graph::{read_graph, read_lines};
+
+// READ CIRCLES. Load a SNAP <ego>.circles file: one user-defined circle per line, a circle name followed by its members' node IDs.
+// Unreadable lines are skipped, as in read_graph
+#[allow(clippy::manual_flatten)]
+pub fn read_circles(file_path: &str) -> Vec<(String, HashSet<usize>)> {
+    let mut circles = Vec::new();
+    if let Ok(lines) = read_lines(file_path) {
+        for line in lines {
+            if let Ok(line) = line {
+                let mut fields = line.split_whitespace();
+                if let Some(name) = fields.next() {
+                    let members: HashSet<usize> = fields.filter_map(|s| s.parse::<usize>().ok()).collect();
+                    circles.push((name.to_string(), members));
+                }
+            }
+        }
+    }
+    circles
+}
+
+// READ FEATURES. Load <prefix>.featnames, <prefix>.feat and <prefix>.egofeat: the feature names (hashtags and mentions), and each node's features as a sorted list of feature indices (a sparse binary vector).
+// The ego's own row in .egofeat is keyed by the ego ID taken from the file name. Unreadable lines are skipped, as in read_graph.
+#[allow(clippy::manual_flatten)]
+pub fn read_features(prefix: &str) -> (Vec<String>, HashMap<usize, Vec<usize>>) {
+    let mut names = Vec::new();
+    if let Ok(lines) = read_lines(format!("{}.featnames", prefix)) {
+        for line in lines {
+            if let Ok(line) = line {
+                let mut fields = line.splitn(2, ' ');
+                if let (Some(Ok(index)), Some(name)) = (fields.next().map(|i| i.parse::<usize>()), fields.next()) {
+                    if names.len() <= index {
+                        names.resize(index + 1, String::new());
+                    }
+                    names[index] = name.trim().to_string();
+                }
+            }
+        }
+    }
+
+    // A row of 0/1 flags becomes the indices of the 1s
+    let active = |flags: &[&str]| -> Vec<usize> {
+        flags.iter().enumerate().filter(|(_, &flag)| flag == "1").map(|(index, _)| index).collect()
+    };
+
+    let mut features = HashMap::new();
+    if let Ok(lines) = read_lines(format!("{}.feat", prefix)) {
+        for line in lines {
+            if let Ok(line) = line {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if let Some(Ok(node)) = fields.first().map(|n| n.parse::<usize>()) {
+                    features.insert(node, active(&fields[1..]));
+                }
+            }
+        }
+    }
+
+    if let (Some(ego), Ok(mut lines)) = (ego_id(prefix), read_lines(format!("{}.egofeat", prefix))) {
+        if let Some(Ok(line)) = lines.next() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            features.insert(ego, active(&fields));
+        }
+    }
+
+    (names, features)
+}
+
+// Ego ID from the file name of a prefix such as data/twitter/12831
+fn ego_id(prefix: &str) -> Option<usize> {
+    Path::new(prefix).file_name().and_then(|name| name.to_str()).and_then(|name| name.parse::<usize>().ok())
+}
+
+// READ EGO NETWORK. Read <prefix>.edges and add the edges from the ego to everyone in it, which the file leaves implicit
+pub fn read_ego_network(prefix: &str) -> HashMap<usize, HashSet<usize>> {
+    let mut graph = read_graph(&format!("{}.edges", prefix));
+    if let Some(ego) = ego_id(prefix) {
+        let alters: Vec<usize> = graph.keys().cloned().filter(|&node| node != ego).collect();
+        for alter in alters {
+            graph.entry(ego).or_default().insert(alter);
+            graph.entry(alter).or_default().insert(ego);
+        }
+    }
+    graph
+}
+
+// EGO PREFIXES. Every <ego>.edges file in a directory, as (ego ID, prefix) pairs sorted by ego ID
+pub fn ego_prefixes(dir: &str) -> Vec<(usize, String)> {
+    let mut prefixes: Vec<(usize, String)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .map_while(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "edges"))
+        .filter_map(|path| {
+            let prefix = path.with_extension("");
+            let prefix = prefix.to_str()?.to_string();
+            Some((ego_id(&prefix)?, prefix))
+        })
+        .collect();
+    prefixes.sort();
+    prefixes
+}
@@ -0,0 +1,431 @@
+// Spectral analysis of an undirected graph: eigenpairs of the adjacency matrix A and the normalized Laplacian L = I - D^-1/2 A D^-1/2, never
+// stored as matrices but applied to vectors straight from the adjacency lists. The k largest or smallest eigenpairs come from thick-restart
+// Lanczos with full reorthogonalization: the Krylov basis grows to a fixed size, the small projected matrix is diagonalized, and the best Ritz
+// vectors are kept as the start of the next basis until their residuals are negligible. A run that is still short of that after
+// MAX_RESTARTS restarts keeps its best Ritz pairs, and eigenpairs says whether that happened.
+// - the spectral gap is lambda_1 - lambda_2 of A: a large gap means random walks mix fast and the graph has no sparse cut
+// - the Fiedler vector belongs to the second-smallest eigenvalue of L (the algebraic connectivity), and its sign splits the graph along a
+//   sparse cut
+// - spectral clustering embeds each node as its row of the k bottom eigenvectors of L (normalized to unit length, as in Ng, Jordan and
+//   Weiss) and groups the rows with k-means
+
+use std::collections::{HashMap, HashSet};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::graph::{connected_components, index_graph, induced_subgraph};
+
+// Krylov basis size beyond the wanted eigenpairs, restarts before giving up on convergence, and the residual at which a Ritz pair is accepted
+// (relative to the largest Ritz value)
+const EXTRA_BASIS: usize = 40;
+const MAX_RESTARTS: usize = 200;
+const TOLERANCE: f64 = 1e-8;
+
+// k-means restarts (the lowest within-cluster sum of squares wins) and Lloyd iterations per restart
+const KMEANS_RESTARTS: usize = 10;
+const KMEANS_ITERATIONS: usize = 100;
+
+// An eigenvalue with its unit eigenvector, by node
+pub type Eigenpair = (f64, HashMap<usize, f64>);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SpectralMatrix {
+    Adjacency,
+    NormalizedLaplacian,
+}
+
+// The matrix as an operator on vectors indexed as in index_graph
+struct Operator {
+    adjacency: Vec<Vec<usize>>,
+    // D^-1/2 for the Laplacian (0 for isolated nodes, whose row of L is the identity's), 1 for the adjacency matrix
+    scale: Vec<f64>,
+    laplacian: bool,
+}
+
+impl Operator {
+    fn new(adjacency: Vec<Vec<usize>>, matrix: SpectralMatrix) -> Self {
+        let laplacian = matrix == SpectralMatrix::NormalizedLaplacian;
+        let scale = adjacency
+            .iter()
+            .map(|neighbors| match (laplacian, neighbors.len()) {
+                (false, _) => 1.0,
+                (true, 0) => 0.0,
+                (true, degree) => 1.0 / (degree as f64).sqrt(),
+            })
+            .collect();
+        Operator { adjacency, scale, laplacian }
+    }
+
+    fn apply(&self, x: &[f64], y: &mut [f64]) {
+        // Scaling once up front leaves a single array to gather from for every neighbor
+        let scaled: Vec<f64> = x.iter().zip(&self.scale).map(|(a, b)| a * b).collect();
+        for (i, neighbors) in self.adjacency.iter().enumerate() {
+            let sum: f64 = neighbors.iter().map(|&j| scaled[j]).sum::<f64>() * self.scale[i];
+            y[i] = if self.laplacian { x[i] - sum } else { sum };
+        }
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalize(v: &mut [f64]) -> f64 {
+    let norm = dot(v, v).sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+    norm
+}
+
+// Remove the components along the (orthonormal) basis; returns the coefficients removed. A second pass runs when the first cancelled most of
+// the vector, since what is left is then dominated by rounding (the criterion of Daniel, Gragg, Kaufman and Stewart).
+fn orthogonalize(v: &mut [f64], basis: &[Vec<f64>]) -> Vec<f64> {
+    let mut coefficients = vec![0.0; basis.len()];
+    let mut norm = dot(v, v).sqrt();
+    for _ in 0..2 {
+        for (coefficient, b) in coefficients.iter_mut().zip(basis) {
+            let c = dot(v, b);
+            *coefficient += c;
+            v.iter_mut().zip(b).for_each(|(x, y)| *x -= c * y);
+        }
+        let remaining = dot(v, v).sqrt();
+        if remaining > 0.7 * norm {
+            break;
+        }
+        norm = remaining;
+    }
+    coefficients
+}
+
+// A random unit vector orthogonal to the locked eigenvectors and the basis
+fn random_vector<R: Rng>(n: usize, locked: &[Vec<f64>], basis: &[Vec<f64>], rng: &mut R) -> Vec<f64> {
+    loop {
+        let mut v: Vec<f64> = (0..n).map(|_| rng.gen::<f64>() - 0.5).collect();
+        orthogonalize(&mut v, locked);
+        orthogonalize(&mut v, basis);
+        if normalize(&mut v) > 1e-10 {
+            return v;
+        }
+    }
+}
+
+// Eigen-decomposition of a small dense symmetric matrix by cyclic Jacobi rotations: eigenvalues in ascending order, with the eigenvectors as
+// the columns of the second matrix
+fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let m = a.len();
+    let mut v: Vec<Vec<f64>> = (0..m).map(|i| (0..m).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+    let scale: f64 = a.iter().flatten().map(|x| x * x).sum::<f64>().max(f64::MIN_POSITIVE);
+
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..m).flat_map(|p| (p + 1..m).map(move |q| (p, q))).map(|(p, q)| a[p][q] * a[p][q]).sum();
+        if off_diagonal <= 1e-30 * scale {
+            break;
+        }
+        for p in 0..m {
+            for q in p + 1..m {
+                if a[p][q].abs() <= 1e-300 {
+                    continue;
+                }
+                // Rotation angle that zeroes a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (rp, rq) = (row[p], row[q]);
+                    row[p] = c * rp - s * rq;
+                    row[q] = s * rp + c * rq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (pr, qr) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    (*pr, *qr) = (c * *pr - s * *qr, s * *pr + c * *qr);
+                }
+                a[p][q] = 0.0;
+                a[q][p] = 0.0;
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+    let values = order.iter().map(|&i| a[i][i]).collect();
+    let vectors = (0..m).map(|row| order.iter().map(|&i| v[row][i]).collect()).collect();
+    (values, vectors)
+}
+
+// Ritz vectors: for each chosen column of the projected eigenvectors, the combination of the basis vectors it gives. Built a block of entries
+// at a time so each block of every vector is read once and stays in cache.
+fn combine(basis: &[Vec<f64>], vectors: &[Vec<f64>], columns: &[usize]) -> Vec<Vec<f64>> {
+    const BLOCK: usize = 512;
+    let n = basis.first().map_or(0, Vec::len);
+    let mut combined = vec![vec![0.0; n]; columns.len()];
+    for start in (0..n).step_by(BLOCK) {
+        let end = (start + BLOCK).min(n);
+        for (b, row) in basis.iter().zip(vectors) {
+            for (y, &column) in combined.iter_mut().zip(columns) {
+                let c = row[column];
+                y[start..end].iter_mut().zip(&b[start..end]).for_each(|(x, v)| *x += c * v);
+            }
+        }
+    }
+    combined
+}
+
+// One thick-restart Lanczos run: the k largest (or smallest) eigenpairs of the operator restricted to the complement of the locked
+// eigenvectors, most extreme first, with unit eigenvectors
+fn lanczos_run<R: Rng>(
+    operator: &Operator,
+    k: usize,
+    largest: bool,
+    locked: &[Vec<f64>],
+    rng: &mut R,
+) -> (Vec<(f64, Vec<f64>)>, bool) {
+    let n = operator.adjacency.len();
+    let dimension = n - locked.len();
+    let k = k.min(dimension);
+    if k == 0 {
+        return (Vec::new(), true);
+    }
+    let m = (2 * k + EXTRA_BASIS).min(dimension);
+    // Ritz vectors carried over each restart: the wanted ones plus a few more to speed up convergence
+    let keep = (k + (m - k) / 3).min(m - 1);
+
+    let mut basis: Vec<Vec<f64>> = vec![random_vector(n, locked, &[], rng)];
+    // Projected matrix basis^T M basis; the first `kept` columns are already known when the basis is extended
+    let mut projected = vec![vec![0.0; m]; m];
+    let mut kept = 0;
+    let mut w = vec![0.0; n];
+    let mut restarts = 0;
+
+    let (values, vectors, wanted, converged) = loop {
+        // Extend the basis to m vectors, projecting each new column against the whole basis
+        let mut residual_norm = 0.0;
+        for j in kept..m {
+            operator.apply(&basis[j], &mut w);
+            let product_norm = dot(&w, &w).sqrt();
+            // The three-term recurrence removes the bulk of the product, so the full pass against the basis rarely has to be repeated
+            let recent = j.saturating_sub(1);
+            let local = orthogonalize(&mut w, &basis[recent..=j]);
+            let mut coefficients = orthogonalize(&mut w, &basis);
+            for (coefficient, c) in coefficients[recent..].iter_mut().zip(local) {
+                *coefficient += c;
+            }
+            for (i, &c) in coefficients.iter().enumerate().take(j + 1) {
+                projected[i][j] = c;
+                projected[j][i] = c;
+            }
+            orthogonalize(&mut w, locked);
+            residual_norm = normalize(&mut w);
+            // A vanishing residual means the basis spans an invariant subspace
+            let breakdown = residual_norm <= 1e-10 * product_norm.max(1.0);
+            if breakdown {
+                residual_norm = 0.0;
+            } else {
+                // Normalizing magnifies whatever rounding left along the locked vectors, and the operator would magnify it further
+                orthogonalize(&mut w, locked);
+                normalize(&mut w);
+            }
+            if j + 1 < m {
+                basis.push(if breakdown { random_vector(n, locked, &basis, rng) } else { w.clone() });
+            }
+        }
+
+        let (values, vectors) = symmetric_eigen(projected.clone());
+        let wanted: Vec<usize> = if largest { (0..m).rev().collect() } else { (0..m).collect() };
+        let spectral_scale = values.iter().map(|v| v.abs()).fold(1.0, f64::max);
+        // The residual of a Ritz pair is the basis residual times the last component of its projected eigenvector
+        let converged = residual_norm == 0.0
+            || wanted.iter().take(k).all(|&i| (residual_norm * vectors[m - 1][i]).abs() <= TOLERANCE * spectral_scale);
+
+        if converged || restarts == MAX_RESTARTS {
+            break (values, vectors, wanted, converged);
+        }
+        restarts += 1;
+
+        // Restart from the kept Ritz vectors and the residual direction
+        let mut new_basis = combine(&basis, &vectors, &wanted[..keep]);
+        projected = vec![vec![0.0; m]; m];
+        for (position, &i) in wanted.iter().take(keep).enumerate() {
+            projected[position][position] = values[i];
+        }
+        orthogonalize(&mut w, &new_basis);
+        normalize(&mut w);
+        new_basis.push(w.clone());
+        basis = new_basis;
+        kept = keep;
+    };
+
+    let ritz_vectors = combine(&basis, &vectors, &wanted[..k]);
+    (wanted.iter().map(|&i| values[i]).zip(ritz_vectors).collect(), converged)
+}
+
+// LANCZOS. The k largest (or smallest) eigenpairs, most extreme first. A single Krylov space holds only one direction of each eigenspace, so a
+// repeated eigenvalue shows up once per run: the pairs found so far are locked and the search repeats in their orthogonal complement until it
+// turns up nothing more extreme than the k-th pair. Also returns whether every run converged.
+fn lanczos<R: Rng>(operator: &Operator, k: usize, largest: bool, rng: &mut R) -> (Vec<(f64, Vec<f64>)>, bool) {
+    let extremeness = |value: f64| if largest { value } else { -value };
+    let mut found: Vec<(f64, Vec<f64>)> = Vec::new();
+    let mut all_converged = true;
+    loop {
+        let locked: Vec<Vec<f64>> = found.iter().map(|(_, vector)| vector.clone()).collect();
+        let (new, converged) = lanczos_run(operator, k, largest, &locked, rng);
+        all_converged &= converged;
+        let threshold = found.get(k.saturating_sub(1)).map(|&(value, _)| extremeness(value));
+        let scale = found.iter().chain(&new).map(|(value, _)| value.abs()).fold(1.0, f64::max);
+        let improved = new.iter().any(|&(value, _)| threshold.is_none_or(|t| extremeness(value) > t + TOLERANCE * scale));
+        if !improved {
+            return (found, all_converged);
+        }
+        found.extend(new);
+        found.sort_by(|a, b| extremeness(b.0).total_cmp(&extremeness(a.0)));
+        found.truncate(k);
+    }
+}
+
+// Eigenpairs on indices from index_graph, and whether they all converged
+fn indexed_eigenpairs<R: Rng>(
+    adjacency: Vec<Vec<usize>>,
+    matrix: SpectralMatrix,
+    k: usize,
+    largest: bool,
+    rng: &mut R,
+) -> (Vec<(f64, Vec<f64>)>, bool) {
+    let (pairs, converged) = lanczos(&Operator::new(adjacency, matrix), k, largest, rng);
+    let pairs = match matrix {
+        SpectralMatrix::Adjacency => pairs,
+        // The normalized Laplacian's spectrum lies in [0, 2]; rounding can leave the zero eigenvalues slightly negative
+        SpectralMatrix::NormalizedLaplacian => pairs.into_iter().map(|(value, vector)| (value.clamp(0.0, 2.0), vector)).collect(),
+    };
+    (pairs, converged)
+}
+
+// EIGENPAIRS. The k largest (or, with largest = false, smallest) eigenvalues of the matrix, most extreme first, each with its unit eigenvector,
+// and whether they all converged within MAX_RESTARTS (if not, they are the best estimates found)
+pub fn eigenpairs<R: Rng>(
+    graph: &HashMap<usize, HashSet<usize>>,
+    matrix: SpectralMatrix,
+    k: usize,
+    largest: bool,
+    rng: &mut R,
+) -> (Vec<Eigenpair>, bool) {
+    let (nodes, adjacency) = index_graph(graph);
+    let (pairs, converged) = indexed_eigenpairs(adjacency, matrix, k, largest, rng);
+    let pairs = pairs.into_iter().map(|(value, vector)| (value, nodes.iter().cloned().zip(vector).collect())).collect();
+    (pairs, converged)
+}
+
+// SPECTRAL GAP. Difference between the two largest eigenvalues of the adjacency matrix
+pub fn spectral_gap<R: Rng>(graph: &HashMap<usize, HashSet<usize>>, rng: &mut R) -> f64 {
+    let (_, adjacency) = index_graph(graph);
+    match indexed_eigenpairs(adjacency, SpectralMatrix::Adjacency, 2, true, rng).0.as_slice() {
+        [(first, _), (second, _), ..] => first - second,
+        _ => 0.0,
+    }
+}
+
+// FIEDLER VECTOR. Algebraic connectivity (second-smallest eigenvalue of the normalized Laplacian) of the largest connected component, and
+// its Fiedler vector D^-1/2 u scaled to unit length, signed so the lowest node ID is non-negative. Positive and negative entries give the two
+// sides of the spectral cut. None if the component has fewer than two nodes.
+pub fn fiedler_vector<R: Rng>(graph: &HashMap<usize, HashSet<usize>>, rng: &mut R) -> Option<(f64, HashMap<usize, f64>)> {
+    let giant: HashSet<usize> = connected_components(graph).into_iter().next()?.into_iter().collect();
+    let (nodes, adjacency) = index_graph(&induced_subgraph(graph, &giant));
+    if nodes.len() < 2 {
+        return None;
+    }
+    let degrees: Vec<f64> = adjacency.iter().map(|neighbors| neighbors.len() as f64).collect();
+    let (value, vector) = indexed_eigenpairs(adjacency, SpectralMatrix::NormalizedLaplacian, 2, false, rng).0.pop()?;
+
+    let mut fiedler: Vec<f64> = vector.iter().zip(&degrees).map(|(x, degree)| x / degree.sqrt()).collect();
+    normalize(&mut fiedler);
+    if fiedler[0] < 0.0 {
+        fiedler.iter_mut().for_each(|x| *x = -*x);
+    }
+    Some((value, nodes.into_iter().zip(fiedler).collect()))
+}
+
+// Lloyd's k-means from k-means++ seeds; returns each point's cluster and the within-cluster sum of squares
+fn kmeans<R: Rng>(points: &[Vec<f64>], k: usize, rng: &mut R) -> (Vec<usize>, f64) {
+    let distance = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>();
+
+    // k-means++: each further center is a point drawn with probability proportional to its squared distance to the nearest center so far
+    let mut centers: Vec<Vec<f64>> = vec![points.choose(rng).unwrap().clone()];
+    let mut nearest: Vec<f64> = points.iter().map(|p| distance(p, &centers[0])).collect();
+    while centers.len() < k {
+        let total: f64 = nearest.iter().sum();
+        let chosen = if total > 0.0 {
+            let mut target = rng.gen::<f64>() * total;
+            nearest.iter().position(|&d| {
+                target -= d;
+                target <= 0.0
+            })
+            .unwrap_or(points.len() - 1)
+        } else {
+            rng.gen_range(0..points.len())
+        };
+        centers.push(points[chosen].clone());
+        for (d, p) in nearest.iter_mut().zip(points) {
+            *d = d.min(distance(p, &centers[centers.len() - 1]));
+        }
+    }
+
+    let mut assignment = vec![usize::MAX; points.len()];
+    for _ in 0..KMEANS_ITERATIONS {
+        let mut changed = false;
+        for (a, p) in assignment.iter_mut().zip(points) {
+            let closest = centers
+                .iter()
+                .map(|center| distance(p, center))
+                .enumerate()
+                .min_by(|x, y| x.1.total_cmp(&y.1))
+                .unwrap()
+                .0;
+            if *a != closest {
+                *a = closest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        // Move each center to the mean of its points (an empty cluster keeps its center)
+        let dimension = points[0].len();
+        let mut sums = vec![vec![0.0; dimension]; k];
+        let mut sizes = vec![0usize; k];
+        for (&a, p) in assignment.iter().zip(points) {
+            sizes[a] += 1;
+            sums[a].iter_mut().zip(p).for_each(|(s, x)| *s += x);
+        }
+        for ((center, sum), size) in centers.iter_mut().zip(sums).zip(sizes) {
+            if size > 0 {
+                *center = sum.into_iter().map(|s| s / size as f64).collect();
+            }
+        }
+    }
+
+    let inertia = assignment.iter().zip(points).map(|(&a, p)| distance(p, &centers[a])).sum();
+    (assignment, inertia)
+}
+
+// SPECTRAL CLUSTERING. Partition into k clusters by k-means on the rows of the k bottom eigenvectors of the normalized Laplacian; returns
+// each node's cluster in 0..k like the community detection algorithms
+pub fn spectral_clustering<R: Rng>(graph: &HashMap<usize, HashSet<usize>>, k: usize, rng: &mut R) -> HashMap<usize, usize> {
+    let (nodes, adjacency) = index_graph(graph);
+    let k = k.min(nodes.len());
+    if k == 0 {
+        return HashMap::new();
+    }
+    let (eigenvectors, _) = indexed_eigenpairs(adjacency, SpectralMatrix::NormalizedLaplacian, k, false, rng);
+    let points: Vec<Vec<f64>> = (0..nodes.len())
+        .map(|node| {
+            let mut row: Vec<f64> = eigenvectors.iter().map(|(_, vector)| vector[node]).collect();
+            normalize(&mut row);
+            row
+        })
+        .collect();
+
+    let (assignment, _) = (0..KMEANS_RESTARTS)
+        .map(|_| kmeans(&points, k, rng))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+    nodes.into_iter().zip(assignment).collect()
+}
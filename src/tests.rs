@@ -1,5 +1,46 @@
 use std::collections::{HashMap, HashSet};
-use crate::graph::{compute_avg_degrees_of_separation, sample_graph, degree_centrality};
+use crate::graph::{compute_avg_degrees_of_separation, mean_distance, sample_graph, degree_centrality, k_hop_neighborhood, ego_network};
+use crate::cliques::{clique_size_distribution, cliques_containing, for_each_maximal_clique};
+use crate::community::{louvain, leiden, modularity, label_propagation, fluid_communities, clique_percolation, ego_splitting};
+use crate::evaluation::{normalized_mutual_information, adjusted_rand_index, best_match_f1, omega_index, partition_to_communities};
+use crate::degree::{degree_ccdf, degree_histogram, fit_power_law, likelihood_ratio_tests, degree_assortativity, average_neighbor_degree, rich_club_coefficient, normalized_rich_club};
+use crate::nullmodel::{double_edge_swap, directed_edge_swap, randomized_copy};
+use crate::analysis::average_clustering;
+use crate::generators::{erdos_renyi_gnp, erdos_renyi_gnm, barabasi_albert, watts_strogatz, chung_lu, configuration_model, stochastic_block_model};
+use crate::diffusion::{DiffusionGraph, DiffusionModel, EdgeProbability, Thresholds};
+use crate::epidemic::{simulate_discrete, simulate_gillespie, EpidemicModel, EpidemicSettings};
+use crate::influence::{greedy_celf, imm};
+use crate::motifs::{count_motifs, describe_motif, motif_significance};
+use crate::directed::{reciprocity, node_reciprocity, triad_census, TRIAD_NAMES};
+use crate::embedding::{node2vec_walks, train_skipgram, most_similar, SkipGramSettings};
+use crate::graph::{index_graph, reverse_graph, to_undirected, pagerank, connected_components, betweenness_centrality};
+use crate::connectivity::{articulation_points, bridges, biconnected_components};
+use crate::robustness::{percolation_curve, robustness_index, RemovalStrategy};
+use crate::paths::{shortest_path, all_shortest_paths, count_shortest_paths, eccentricities, eccentricity_summary};
+use crate::spectral::{eigenpairs, spectral_gap, fiedler_vector, spectral_clustering, SpectralMatrix};
+use crate::snap::{read_circles, read_features, read_ego_network, ego_prefixes};
+use crate::attributes::feature_homophily;
+use crate::recommend::{personalized_pagerank, random_walk_with_restart, top_walk_recommendations, who_to_follow};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// Two 4-node cliques joined by a single edge between nodes 4 and 5
+fn create_two_cliques() -> HashMap<usize, HashSet<usize>> {
+    let mut graph: HashMap<usize, HashSet<usize>> = HashMap::new();
+    let mut connect = |a: usize, b: usize| {
+        graph.entry(a).or_default().insert(b);
+        graph.entry(b).or_default().insert(a);
+    };
+    for group in [[1, 2, 3, 4], [5, 6, 7, 8]] {
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                connect(group[i], group[j]);
+            }
+        }
+    }
+    connect(4, 5);
+    graph
+}
 
 // Create a simple test graph
 fn create_test_graph() -> HashMap<usize, HashSet<usize>> {
@@ -11,6 +52,14 @@ fn create_test_graph() -> HashMap<usize, HashSet<usize>> {
     graph
 }
 
+// Create an empty directory for one test's files, unique to this process so concurrent or earlier runs cannot collide
+fn scratch_dir(test: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("ntfinal_{}_{}", std::process::id(), test));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,6 +92,7 @@ mod tests {
 
     // Average degrees of separation
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_compute_avg_degrees_of_separation() {
         let graph = create_test_graph();
         let avg_separation = compute_avg_degrees_of_separation(&graph, 1);
@@ -50,4 +100,796 @@ mod tests {
         // Check that the average degrees of separation is within expected range
         assert!(avg_separation >= 1.0 && avg_separation <= 2.0);
     }
-}
\ No newline at end of file
+
+    // Mean distance to the other reachable nodes
+    #[test]
+    fn test_mean_distance() {
+        let graph = create_test_graph();
+        // Nodes 2 and 3 are one hop from node 1 and node 4 is two
+        assert!((mean_distance(&graph, 1) - 4.0 / 3.0).abs() < 1e-12);
+        let isolated = HashMap::from([(7, HashSet::new())]);
+        assert_eq!(mean_distance(&isolated, 7), 0.0);
+    }
+
+    // Personalized PageRank recommendations reach past two hops
+    #[test]
+    fn test_personalized_pagerank() {
+        let graph = create_test_graph();
+        let scores = personalized_pagerank(&graph, 1, 0.15, 100);
+
+        // Scores form a probability distribution
+        let total: f64 = scores.values().sum();
+        assert!((total - 1.0).abs() < 1e-6);
+
+        // Node 4 is only reachable through node 3, so it is the one recommendation for node 1
+        let suggestions = top_walk_recommendations(&graph, 1, &scores);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].0, 4);
+    }
+
+    // Monte Carlo random walk with restart agrees with power iteration
+    #[test]
+    fn test_random_walk_with_restart() {
+        let graph = create_test_graph();
+        let exact = personalized_pagerank(&graph, 1, 0.15, 100);
+        let estimate = random_walk_with_restart(&graph, 1, 0.15, 20_000, &mut StdRng::seed_from_u64(7));
+
+        for (node, score) in &exact {
+            assert!((estimate[node] - score).abs() < 0.02);
+        }
+    }
+
+    // Who to follow on a directed graph
+    #[test]
+    fn test_who_to_follow() {
+        // 1 follows 2 and 3; both follow 4, only 3 follows 5, and 5 follows 1 back
+        let mut graph = HashMap::new();
+        graph.insert(1, HashSet::from([2, 3]));
+        graph.insert(2, HashSet::from([4]));
+        graph.insert(3, HashSet::from([4, 5]));
+        graph.insert(4, HashSet::new());
+        graph.insert(5, HashSet::from([1]));
+
+        let suggestions = who_to_follow(&graph, 1, 0.0);
+        assert_eq!(suggestions, vec![(4, 2.0), (5, 1.0)]);
+
+        // A follow-back boost lifts node 5 above node 4
+        let suggestions = who_to_follow(&graph, 1, 1.5);
+        assert_eq!(suggestions, vec![(5, 2.5), (4, 2.0)]);
+    }
+
+    // node2vec walks follow edges and the trained embeddings place neighbors close together
+    #[test]
+    fn test_node_embeddings() {
+        // Two triangles joined by a single edge between nodes 3 and 4
+        let mut graph = HashMap::new();
+        graph.insert(1, HashSet::from([2, 3]));
+        graph.insert(2, HashSet::from([1, 3]));
+        graph.insert(3, HashSet::from([1, 2, 4]));
+        graph.insert(4, HashSet::from([3, 5, 6]));
+        graph.insert(5, HashSet::from([4, 6]));
+        graph.insert(6, HashSet::from([4, 5]));
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let (nodes, adjacency) = index_graph(&graph);
+        let walks = node2vec_walks(&adjacency, 20, 10, 1.0, 0.5, &mut rng);
+        assert_eq!(walks.len(), 6 * 20);
+        for walk in &walks {
+            assert_eq!(walk.len(), 10);
+            for step in walk.windows(2) {
+                assert!(adjacency[step[0]].contains(&step[1]));
+            }
+        }
+
+        let settings = SkipGramSettings { dimensions: 16, epochs: 5, ..SkipGramSettings::default() };
+        let embeddings = train_skipgram(&walks, nodes.len(), &settings, &mut rng);
+        let similar = most_similar(&nodes, &embeddings, 1, &HashSet::new(), 5);
+        assert_eq!(similar.len(), 5);
+        // Node 1's own triangle is more similar to it than the far triangle
+        let rank = |node: usize| similar.iter().position(|&(n, _)| n == node).unwrap();
+        assert!(rank(2) < rank(5) && rank(2) < rank(6));
+    }
+
+    // Bidirectional BFS returns a valid shortest chain
+    #[test]
+    fn test_shortest_path() {
+        let graph = create_test_graph();
+        assert_eq!(shortest_path(&graph, 1, 4), Some(vec![1, 3, 4]));
+        assert_eq!(shortest_path(&graph, 4, 4), Some(vec![4]));
+        assert_eq!(shortest_path(&graph, 1, 99), None);
+
+        // Disconnected pair
+        let mut graph = graph;
+        graph.insert(5, HashSet::from([6]));
+        graph.insert(6, HashSet::from([5]));
+        assert_eq!(shortest_path(&graph, 1, 6), None);
+    }
+
+    // Enumerating and counting every shortest path
+    #[test]
+    fn test_all_shortest_paths() {
+        // A square 1-2-4-3-1 with a tail 4-5: two shortest paths from 1 to 5
+        let mut graph = HashMap::new();
+        graph.insert(1, HashSet::from([2, 3]));
+        graph.insert(2, HashSet::from([1, 4]));
+        graph.insert(3, HashSet::from([1, 4]));
+        graph.insert(4, HashSet::from([2, 3, 5]));
+        graph.insert(5, HashSet::from([4]));
+
+        assert_eq!(all_shortest_paths(&graph, 1, 5), vec![vec![1, 2, 4, 5], vec![1, 3, 4, 5]]);
+        assert_eq!(count_shortest_paths(&graph, 1, 5), 2);
+        assert_eq!(count_shortest_paths(&graph, 2, 3), 2);
+        assert_eq!(shortest_path(&graph, 1, 5).unwrap().len(), 4);
+    }
+
+    // k-hop neighborhoods and ego networks
+    #[test]
+    fn test_ego_network() {
+        let graph = create_test_graph();
+        let distances = k_hop_neighborhood(&graph, 4, 1);
+        assert_eq!(distances, HashMap::from([(4, 0), (3, 1)]));
+        assert_eq!(k_hop_neighborhood(&graph, 4, 2).len(), 4);
+
+        // Radius 1 around node 1 is the triangle 1-2-3, without the 3-4 edge
+        let (ego_graph, ego_nodes) = ego_network(&graph, 1, 1);
+        assert_eq!(ego_nodes, HashSet::from([1, 2, 3]));
+        assert_eq!(ego_graph[&3], HashSet::from([1, 2]));
+        assert!(!ego_graph.contains_key(&4));
+    }
+
+    // Louvain and Leiden separate two cliques joined by one edge
+    #[test]
+    fn test_louvain_and_leiden() {
+        let graph = create_two_cliques();
+        for (communities, quality) in [
+            louvain(&graph, 1.0, &mut StdRng::seed_from_u64(1)),
+            leiden(&graph, 1.0, &mut StdRng::seed_from_u64(1)),
+        ] {
+            assert_eq!(communities.len(), 8);
+            assert_eq!(communities[&1], communities[&4]);
+            assert_eq!(communities[&5], communities[&8]);
+            assert_ne!(communities[&1], communities[&5]);
+            // 12 of the 13 edges are internal: Q = 12/13 - 2 * (13/26)^2
+            assert!((quality - (12.0 / 13.0 - 0.5)).abs() < 1e-9);
+            assert!((modularity(&graph, &communities, 1.0) - quality).abs() < 1e-9);
+        }
+
+        // Everything in one community has zero modularity
+        let single: HashMap<usize, usize> = graph.keys().map(|&node| (node, 0)).collect();
+        assert!(modularity(&graph, &single, 1.0).abs() < 1e-9);
+    }
+
+    // Label propagation and fluid communities return the same assignment structure
+    #[test]
+    fn test_label_propagation_and_fluid() {
+        let graph = create_two_cliques();
+        let mut rng = StdRng::seed_from_u64(2);
+        let asynchronous = label_propagation(&graph, false, 100, &mut rng);
+        let fluid = fluid_communities(&graph, 2, 100, &mut rng);
+
+        for communities in [&asynchronous, &fluid] {
+            assert_eq!(communities.len(), 8);
+            // Each clique ends up within a single community
+            assert_eq!(communities[&1], communities[&2]);
+            assert_eq!(communities[&6], communities[&7]);
+        }
+        // Fluid communities with k = 2 splits exactly at the bridge
+        assert_ne!(fluid[&1], fluid[&8]);
+        assert_eq!(fluid.values().collect::<HashSet<_>>().len(), 2);
+
+        // Synchronous updates stop after max_iterations even if they never settle
+        let synchronous = label_propagation(&graph, true, 10, &mut rng);
+        assert_eq!(synchronous.len(), 8);
+    }
+
+    // Loading SNAP circles and scoring communities against them
+    #[test]
+    fn test_circles_evaluation() {
+        let dir = scratch_dir("circles_evaluation");
+        let path = dir.join("0.circles");
+        std::fs::write(&path, "circle0\t1\t2\t3\ncircle1\t3\t4\n").unwrap();
+        let circles = read_circles(path.to_str().unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(circles.len(), 2);
+        assert_eq!(circles[0], ("circle0".to_string(), HashSet::from([1, 2, 3])));
+
+        // Identical partitions (up to relabeling) score perfectly
+        let a = HashMap::from([(1, 0), (2, 0), (3, 1), (4, 1)]);
+        let relabeled = HashMap::from([(1, 5), (2, 5), (3, 9), (4, 9)]);
+        assert!((normalized_mutual_information(&a, &relabeled) - 1.0).abs() < 1e-9);
+        assert!((adjusted_rand_index(&a, &relabeled) - 1.0).abs() < 1e-9);
+        let communities = partition_to_communities(&a);
+        assert!((best_match_f1(&communities, &partition_to_communities(&relabeled)) - 1.0).abs() < 1e-9);
+
+        // Agreement on exactly the pairs expected by chance gives an adjusted Rand index of 0
+        let b = HashMap::from([(1, 0), (2, 0), (3, 0), (4, 1)]);
+        assert!(adjusted_rand_index(&a, &b).abs() < 1e-9);
+
+        // Omega index on overlapping covers
+        let nodes = HashSet::from([1, 2, 3, 4]);
+        let cover: Vec<HashSet<usize>> = circles.into_iter().map(|(_, members)| members).collect();
+        assert!((omega_index(&cover, &cover, &nodes) - 1.0).abs() < 1e-9);
+        assert!(omega_index(&communities, &cover, &nodes) < 1.0);
+    }
+
+    // Loading SNAP feature files and measuring attribute homophily
+    #[test]
+    fn test_feature_homophily() {
+        let dir = scratch_dir("feature_homophily");
+        let prefix = dir.join("77");
+        let prefix = prefix.to_str().unwrap();
+        std::fs::write(format!("{}.featnames", prefix), "0 #bostonsports\n1 @espn\n").unwrap();
+        std::fs::write(format!("{}.feat", prefix), "1 1 0\n2 1 1\n3 0 1\n").unwrap();
+        std::fs::write(format!("{}.egofeat", prefix), "0 0\n").unwrap();
+        let (names, features) = read_features(prefix);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(names, vec!["#bostonsports".to_string(), "@espn".to_string()]);
+        assert_eq!(features[&2], vec![0, 1]);
+        assert_eq!(features[&77], Vec::<usize>::new());
+
+        // Path 1-2-3-77 where only 1 and 2 use #bostonsports
+        let mut graph = HashMap::new();
+        graph.insert(1, HashSet::from([2]));
+        graph.insert(2, HashSet::from([1, 3]));
+        graph.insert(3, HashSet::from([2, 77]));
+        graph.insert(77, HashSet::from([3]));
+        let results = feature_homophily(&graph, &features);
+        let (feature, holders, homophily, assortativity) = results[0];
+        assert_eq!((feature, holders), (0, 2));
+        assert!((homophily - 2.0 / 3.0).abs() < 1e-9);
+        assert!((assortativity - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    // Per-ego networks include the implicit edges from the ego to everyone
+    #[test]
+    fn test_read_ego_network() {
+        let dir = scratch_dir("read_ego_network");
+        std::fs::write(dir.join("9.edges"), "1 2\n2 3\n").unwrap();
+        std::fs::write(dir.join("4.edges"), "5 6\n").unwrap();
+        std::fs::write(dir.join("9.circles"), "circle0\t1\t2\n").unwrap();
+
+        let prefixes = ego_prefixes(dir.to_str().unwrap());
+        assert_eq!(prefixes.iter().map(|(ego, _)| *ego).collect::<Vec<_>>(), vec![4, 9]);
+
+        let graph = read_ego_network(&prefixes[1].1);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(graph[&9], HashSet::from([1, 2, 3]));
+        assert_eq!(graph[&2], HashSet::from([1, 3, 9]));
+    }
+
+    // Bron–Kerbosch finds every maximal clique exactly once
+    #[test]
+    fn test_maximal_cliques() {
+        let graph = create_test_graph();
+        let mut found = Vec::new();
+        for_each_maximal_clique(&graph, |clique| {
+            let mut clique = clique.to_vec();
+            clique.sort_unstable();
+            found.push(clique);
+        });
+        found.sort();
+        assert_eq!(found, vec![vec![1, 2, 3], vec![3, 4]]);
+
+        let (distribution, maximum) = clique_size_distribution(&create_two_cliques());
+        assert_eq!(distribution.into_iter().collect::<Vec<_>>(), vec![(2, 1), (4, 2)]);
+        assert_eq!(maximum.len(), 4);
+
+        assert_eq!(cliques_containing(&graph, 4), vec![vec![3, 4]]);
+        assert_eq!(cliques_containing(&graph, 3), vec![vec![1, 2, 3], vec![3, 4]]);
+    }
+
+    // Overlapping covers put a shared node in several communities
+    #[test]
+    fn test_overlapping_communities() {
+        // Triangles 1-2-3 and 2-3-4 share an edge; triangle 4-5-6 only shares node 4
+        let mut graph: HashMap<usize, HashSet<usize>> = HashMap::new();
+        for (a, b) in [(1, 2), (1, 3), (2, 3), (2, 4), (3, 4), (4, 5), (4, 6), (5, 6)] {
+            graph.entry(a).or_default().insert(b);
+            graph.entry(b).or_default().insert(a);
+        }
+        let cover = clique_percolation(&graph, 3);
+        assert_eq!(cover[&1], cover[&4][..1]);
+        assert_eq!(cover[&4].len(), 2);
+        assert_eq!(cover[&5], cover[&4][1..]);
+
+        // Node 0 bridges two cliques that share nothing else, so ego-splitting gives it two personas in two communities
+        let mut graph = create_two_cliques();
+        graph.get_mut(&4).unwrap().remove(&5);
+        graph.get_mut(&5).unwrap().remove(&4);
+        for node in [1, 2, 3, 4, 5, 6, 7, 8] {
+            graph.entry(0).or_default().insert(node);
+            graph.get_mut(&node).unwrap().insert(0);
+        }
+        let cover = ego_splitting(&graph, 1.0, &mut StdRng::seed_from_u64(1));
+        assert_eq!(cover[&0].len(), 2);
+        assert_eq!(cover[&1].len(), 1);
+        assert_ne!(cover[&1], cover[&8]);
+    }
+
+    // Degree distribution summaries and power-law fitting
+    #[test]
+    fn test_degree_distribution() {
+        let degrees = vec![1, 1, 2, 3, 3, 3];
+        assert_eq!(degree_histogram(&degrees).into_iter().collect::<Vec<_>>(), vec![(1, 2), (2, 1), (3, 3)]);
+        assert_eq!(degree_ccdf(&degrees), vec![(1, 1.0), (2, 4.0 / 6.0), (3, 0.5)]);
+
+        // Degrees drawn from a discrete power law with alpha = 2.5 are recognized as one
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut cumulative = Vec::new();
+        let mut total = 0.0;
+        for k in 1..100_000 {
+            total += (k as f64).powf(-2.5);
+            cumulative.push(total);
+        }
+        let power_law: Vec<usize> = (0..5000)
+            .map(|_| {
+                let u: f64 = rand::Rng::gen(&mut rng);
+                1 + cumulative.partition_point(|&c| c < u * total)
+            })
+            .collect();
+        let fit = fit_power_law(&power_law).unwrap();
+        assert!((fit.alpha - 2.5).abs() < 0.15);
+        let tests = likelihood_ratio_tests(&power_law, &fit);
+        let (name, ratio, p_value) = tests[1];
+        assert_eq!(name, "exponential");
+        assert!(ratio > 0.0 && p_value < 0.1);
+
+        // Geometric degrees are never judged power-law distributed
+        let geometric: Vec<usize> = (0..5000)
+            .map(|_| {
+                let u: f64 = rand::Rng::gen(&mut rng);
+                1 + ((1.0 - u).ln() / 0.8f64.ln()).floor() as usize
+            })
+            .collect();
+        let fit = fit_power_law(&geometric).unwrap();
+        let (_, ratio, p_value) = likelihood_ratio_tests(&geometric, &fit)[1];
+        assert!(ratio < 0.0 || p_value >= 0.1);
+    }
+
+    // Degree correlations and the degree-preserving null model
+    #[test]
+    fn test_degree_correlations() {
+        // Every edge of a star joins the hub to a leaf: perfectly disassortative
+        let star: HashMap<usize, HashSet<usize>> = (1..=5)
+            .map(|leaf| (leaf, HashSet::from([0])))
+            .chain([(0, (1..=5).collect())])
+            .collect();
+        let degrees = degree_centrality(&star);
+        assert!((degree_assortativity(&star, &degrees, &degrees) + 1.0).abs() < 1e-9);
+
+        let graph = create_two_cliques();
+        let degrees = degree_centrality(&graph);
+        assert!((degree_assortativity(&graph, &degrees, &degrees) + 1.0 / 12.0).abs() < 1e-9);
+        let knn = average_neighbor_degree(&graph, &degrees, &degrees);
+        assert!((knn[&3] - 10.0 / 3.0).abs() < 1e-9);
+        assert!((knn[&4] - 3.25).abs() < 1e-9);
+
+        // The two bridge nodes are the only ones with degree above 3, and they are linked
+        let rich_club = rich_club_coefficient(&graph);
+        assert!((rich_club[&0] - 26.0 / 56.0).abs() < 1e-9);
+        assert_eq!(rich_club[&3], 1.0);
+        assert!(!rich_club.contains_key(&4));
+
+        // Swaps keep every degree and never add self-loops
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut randomized = graph.clone();
+        assert!(double_edge_swap(&mut randomized, 20, 1000, &mut rng) > 0);
+        assert_ne!(randomized, graph);
+        assert_eq!(degree_centrality(&randomized), degrees);
+        assert!(randomized.iter().all(|(node, neighbors)| !neighbors.contains(node)));
+
+        let normalized = normalized_rich_club(&graph, 3, 10, &mut rng);
+        assert!((normalized[&0].1 - 1.0).abs() < 1e-9);
+    }
+
+    // Reciprocity and the directed triad census
+    #[test]
+    fn test_reciprocity_and_triads() {
+        // 1 <-> 2, 2 -> 3, 3 -> 1, and 4 follows nobody and has no followers
+        let graph: HashMap<usize, HashSet<usize>> = HashMap::from([
+            (1, HashSet::from([2])),
+            (2, HashSet::from([1, 3])),
+            (3, HashSet::from([1])),
+            (4, HashSet::new()),
+        ]);
+        assert!((reciprocity(&graph) - 0.5).abs() < 1e-9);
+        let per_node = node_reciprocity(&graph);
+        assert!((per_node[&1] - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(per_node[&3], 0.0);
+        assert!(!per_node.contains_key(&4));
+
+        // The triangle is 120C (a mutual pair plus a path 2 -> 3 -> 1 between them); each triple with node 4 keeps one dyad of the triangle
+        let census = triad_census(&graph);
+        assert_eq!(count_of(&census, "120C"), 1);
+        assert_eq!(count_of(&census, "102"), 1);
+        assert_eq!(count_of(&census, "012"), 2);
+        assert_eq!(census.iter().sum::<u64>(), 4);
+
+        // A directed cycle versus a transitive triple
+        let cycle = HashMap::from([(1, HashSet::from([2])), (2, HashSet::from([3])), (3, HashSet::from([1]))]);
+        assert_eq!(count_of(&triad_census(&cycle), "030C"), 1);
+        let transitive = HashMap::from([(1, HashSet::from([2, 3])), (2, HashSet::from([3])), (3, HashSet::new())]);
+        assert_eq!(count_of(&triad_census(&transitive), "030T"), 1);
+    }
+
+    fn count_of(census: &[u64; 16], name: &str) -> u64 {
+        census[TRIAD_NAMES.iter().position(|&n| n == name).unwrap()]
+    }
+
+    // Motif counts and their significance against randomized graphs
+    #[test]
+    fn test_motifs() {
+        // Each clique holds 4 triangles; the bridge adds 6 open paths (3 on each side)
+        let graph = create_two_cliques();
+        let counts = count_motifs(&graph, 3, false);
+        let by_edges: HashMap<usize, u64> = counts.iter().map(|(&code, &count)| (describe_motif(code, 3, false).split(' ').count(), count)).collect();
+        assert_eq!(by_edges, HashMap::from([(3, 8), (2, 6)]));
+
+        // 4-node sets: each clique, the bridge with two nodes from the same side (3 tailed triangles per side), and 3 * 3 paths across it
+        let counts = count_motifs(&graph, 4, false);
+        assert_eq!(counts.values().sum::<u64>(), 2 + 2 * 3 + 9);
+
+        // A 3-cycle and a transitive triple are different directed motifs
+        let cycle = HashMap::from([(1, HashSet::from([2])), (2, HashSet::from([3])), (3, HashSet::from([1]))]);
+        let transitive = HashMap::from([(1, HashSet::from([2, 3])), (2, HashSet::from([3])), (3, HashSet::new())]);
+        let cycle_counts = count_motifs(&cycle, 3, true);
+        let transitive_counts = count_motifs(&transitive, 3, true);
+        assert_eq!(cycle_counts.values().sum::<u64>(), 1);
+        assert_eq!(transitive_counts.values().sum::<u64>(), 1);
+        assert_ne!(cycle_counts.keys().next(), transitive_counts.keys().next());
+
+        // Directed swaps keep in- and out-degrees
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut directed: HashMap<usize, HashSet<usize>> = (0..10).map(|i| (i, HashSet::from([(i + 1) % 10, (i + 3) % 10]))).collect();
+        let original = directed.clone();
+        assert!(directed_edge_swap(&mut directed, 10, 1000, &mut rng) > 0);
+        assert_eq!(degree_centrality(&directed), degree_centrality(&original));
+        assert_eq!(degree_centrality(&reverse_graph(&directed)), degree_centrality(&reverse_graph(&original)));
+
+        // Two dense cliques have more triangles than random graphs with the same degrees
+        let scores = motif_significance(&graph, 3, false, 5, 10, &mut rng);
+        let triangle = scores.iter().find(|s| s.count == 8).unwrap();
+        assert!(triangle.z_score > 0.0 && triangle.random_mean < 8.0);
+    }
+
+    // Random graph generators
+    #[test]
+    fn test_generators() {
+        let edges = |graph: &HashMap<usize, HashSet<usize>>| graph.values().map(HashSet::len).sum::<usize>() / 2;
+        let mut rng = StdRng::seed_from_u64(11);
+
+        // p = 1 gives the complete graph, which exercises every pair index
+        let complete = erdos_renyi_gnp(30, 1.0, &mut rng);
+        assert_eq!(edges(&complete), 435);
+        let sparse = erdos_renyi_gnp(2000, 0.005, &mut rng);
+        assert_eq!(sparse.len(), 2000);
+        assert!((edges(&sparse) as f64 - 9995.0).abs() < 400.0);
+
+        assert_eq!(edges(&erdos_renyi_gnm(100, 300, &mut rng)), 300);
+        assert_eq!(edges(&erdos_renyi_gnm(10, 40, &mut rng)), 40);
+        assert_eq!(edges(&barabasi_albert(500, 3, &mut rng)), 497 * 3);
+
+        // Rewiring keeps the number of edges of the ring lattice
+        assert_eq!(edges(&watts_strogatz(100, 6, 0.0, &mut rng)), 300);
+        let small_world = watts_strogatz(100, 6, 0.2, &mut rng);
+        assert_eq!(edges(&small_world), 300);
+        assert!(small_world.iter().all(|(node, neighbors)| !neighbors.contains(node)));
+
+        // Expected degrees follow the weights
+        let weights: Vec<f64> = (0..1000).map(|i| if i < 100 { 40.0 } else { 4.0 }).collect();
+        let graph = chung_lu(&weights, &mut rng);
+        let hub_mean = (0..100).map(|i| graph[&i].len()).sum::<usize>() as f64 / 100.0;
+        assert!((hub_mean - 40.0).abs() < 4.0, "{}", hub_mean);
+
+        // The erased configuration model never exceeds the requested degrees
+        let degrees: Vec<usize> = (0..200).map(|i| 1 + i % 7).collect();
+        let graph = configuration_model(&degrees, &mut rng);
+        assert!((0..200).all(|i| graph[&i].len() <= degrees[i]));
+        assert!(edges(&graph) as f64 > 0.9 * degrees.iter().sum::<usize>() as f64 / 2.0);
+
+        // Dense blocks with nothing between them are disjoint cliques
+        let blocks = stochastic_block_model(&[4, 5], &[vec![1.0, 0.0], vec![0.0, 1.0]], &mut rng);
+        assert_eq!(edges(&blocks), 6 + 10);
+        assert!(blocks[&3].iter().all(|&n| n < 4) && blocks[&4].iter().all(|&n| n >= 4));
+
+        // Seeded generators are reproducible
+        let first = barabasi_albert(200, 2, &mut StdRng::seed_from_u64(3));
+        let second = barabasi_albert(200, 2, &mut StdRng::seed_from_u64(3));
+        assert_eq!(first, second);
+    }
+
+    // Clustering and degree-preserving randomized copies
+    #[test]
+    fn test_null_model() {
+        let graph = create_two_cliques();
+        assert!((average_clustering(&graph) - 0.875).abs() < 1e-9);
+
+        // Randomizing breaks up the cliques but keeps every degree
+        let mut rng = StdRng::seed_from_u64(2);
+        let randomized = randomized_copy(&graph, false, 10, &mut rng);
+        assert_eq!(degree_centrality(&randomized), degree_centrality(&graph));
+        assert!(average_clustering(&randomized) < 0.875);
+
+        // Directed copies keep in- and out-degrees, and forgetting direction merges mutual follows
+        let directed = HashMap::from([(1, HashSet::from([2])), (2, HashSet::from([1, 3])), (3, HashSet::new())]);
+        assert_eq!(to_undirected(&directed), HashMap::from([(1, HashSet::from([2])), (2, HashSet::from([1, 3])), (3, HashSet::from([2]))]));
+        let follows: HashMap<usize, HashSet<usize>> = (0..12).map(|i| (i, HashSet::from([(i + 1) % 12, (i + 5) % 12]))).collect();
+        let randomized = randomized_copy(&follows, true, 10, &mut rng);
+        assert_eq!(degree_centrality(&randomized), degree_centrality(&follows));
+        assert_eq!(degree_centrality(&reverse_graph(&randomized)), degree_centrality(&reverse_graph(&follows)));
+    }
+
+    // Independent cascade and linear threshold spread
+    #[test]
+    fn test_diffusion() {
+        // 2 and 3 follow 1, 4 follows 2 and 3, 5 follows 4: tweets flow 1 -> {2, 3} -> 4 -> 5
+        let follows = HashMap::from([
+            (1, HashSet::new()),
+            (2, HashSet::from([1])),
+            (3, HashSet::from([1])),
+            (4, HashSet::from([2, 3])),
+            (5, HashSet::from([4])),
+        ]);
+        let graph = DiffusionGraph::new(&follows);
+        let seeds = [graph.index_of(1).unwrap()];
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let certain = DiffusionModel::IndependentCascade(EdgeProbability::Uniform(1.0));
+        assert_eq!(graph.simulate(certain, &seeds, &mut rng), vec![1, 3, 4, 5]);
+        let never = DiffusionModel::IndependentCascade(EdgeProbability::Uniform(0.0));
+        assert_eq!(graph.simulate(never, &seeds, &mut rng), vec![1]);
+
+        // Account 4 needs both accounts it follows active to reach a threshold of 1, and either one for 0.5
+        let unanimous = DiffusionModel::LinearThreshold(Thresholds::Fixed(1.0));
+        assert_eq!(graph.simulate(unanimous, &[graph.index_of(2).unwrap()], &mut rng), vec![1]);
+        assert_eq!(graph.simulate(unanimous, &seeds, &mut rng), vec![1, 3, 4, 5]);
+        let half = DiffusionModel::LinearThreshold(Thresholds::Fixed(0.5));
+        assert_eq!(graph.simulate(half, &[graph.index_of(2).unwrap()], &mut rng), vec![1, 2, 3]);
+
+        // Weighted cascade: 2 and 3 always activate, 4 with probability 1 - (1/2)^2 = 3/4, then 5 always
+        let estimate = graph.expected_spread(DiffusionModel::IndependentCascade(EdgeProbability::WeightedCascade), &seeds, 4000, 7);
+        assert!((estimate.mean - (3.0 + 2.0 * 0.75)).abs() < 0.1);
+        assert_eq!(estimate.curve.len(), 4);
+        assert_eq!(estimate.curve[1], 3.0);
+        let again = graph.expected_spread(DiffusionModel::IndependentCascade(EdgeProbability::WeightedCascade), &seeds, 4000, 7);
+        assert_eq!(estimate.mean, again.mean);
+    }
+
+    // Influence maximization picks seeds with different audiences
+    #[test]
+    fn test_influence_maximization() {
+        // Accounts 1 and 2 share the same five followers; account 3 has three followers of its own
+        let mut follows: HashMap<usize, HashSet<usize>> = HashMap::new();
+        for follower in 10..15 {
+            follows.insert(follower, HashSet::from([1, 2]));
+        }
+        for follower in 20..23 {
+            follows.insert(follower, HashSet::from([3]));
+        }
+        for account in 1..=3 {
+            follows.insert(account, HashSet::new());
+        }
+        let graph = DiffusionGraph::new(&follows);
+        let certain = DiffusionModel::IndependentCascade(EdgeProbability::Uniform(1.0));
+        let chosen = |indices: &[usize]| indices.iter().map(|&i| graph.nodes[i]).collect::<HashSet<usize>>();
+
+        let candidates: Vec<usize> = (0..graph.nodes.len()).collect();
+        for plus_plus in [false, true] {
+            let seeds = greedy_celf(&graph, certain, &candidates, 2, 10, 1, plus_plus);
+            let indices: Vec<usize> = seeds.iter().map(|&(index, _)| index).collect();
+            assert!(chosen(&indices).contains(&3));
+            assert_eq!(seeds[1].1, 10.0);
+        }
+
+        let (seeds, estimate) = imm(&graph, certain, 2, 0.5, 1).unwrap();
+        assert!(chosen(&seeds).contains(&3));
+        assert!((estimate - 10.0).abs() < 1.5);
+        assert!(imm(&graph, DiffusionModel::LinearThreshold(Thresholds::Fixed(0.5)), 2, 0.5, 1).is_none());
+
+        // PageRank sums to 1; account 3's followers pass it all their rank while 1 and 2 split theirs
+        let ranks = pagerank(&follows, 0.85, 100);
+        assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(ranks[&3] > ranks[&1] && ranks[&1] > ranks[&20]);
+        assert!((ranks[&1] - ranks[&2]).abs() < 1e-12);
+    }
+
+    // SIR and SIS epidemics, in discrete and continuous time
+    #[test]
+    fn test_epidemics() {
+        // A chain of followers: 2 follows 1, 3 follows 2, 4 follows 3
+        let follows = HashMap::from([(1, HashSet::new()), (2, HashSet::from([1])), (3, HashSet::from([2])), (4, HashSet::from([3]))]);
+        let graph = DiffusionGraph::new(&follows);
+        let initial = [graph.index_of(1).unwrap()];
+        let mut rng = StdRng::seed_from_u64(6);
+
+        // Certain infection and recovery moves a single infected account down the chain
+        let certain = EpidemicSettings { model: EpidemicModel::Sir, infection_rate: 1.0, recovery_rate: 1.0 };
+        let series = simulate_discrete(&graph, certain, &initial, 100, &mut rng);
+        assert_eq!(series, vec![(0.0, 3, 1, 0), (1.0, 2, 1, 1), (2.0, 1, 1, 2), (3.0, 0, 1, 3), (4.0, 0, 0, 4)]);
+        let sis = EpidemicSettings { model: EpidemicModel::Sis, ..certain };
+        assert_eq!(simulate_discrete(&graph, sis, &initial, 100, &mut rng).last(), Some(&(4.0, 4, 0, 0)));
+
+        // Without recovery everyone downstream is eventually infected, and the run stops once nobody is left to infect
+        let no_recovery = EpidemicSettings { model: EpidemicModel::Sir, infection_rate: 2.0, recovery_rate: 0.0 };
+        let series = simulate_gillespie(&graph, no_recovery, &initial, 50.0, 1.0, &mut rng);
+        let &(end, s, i, r) = series.last().unwrap();
+        assert!(end < 50.0);
+        assert_eq!((s, i, r), (0, 4, 0));
+        assert!(series.iter().all(|&(_, s, i, r)| s + i + r == 4));
+        assert!(series.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        // Without transmission the seed simply recovers, after about 1 / recovery_rate time units on average
+        let no_spread = EpidemicSettings { model: EpidemicModel::Sir, infection_rate: 0.0, recovery_rate: 2.0 };
+        let mut total_time = 0.0;
+        for _ in 0..2000 {
+            let series = simulate_gillespie(&graph, no_spread, &initial, 100.0, 10.0, &mut rng);
+            let &(time, s, i, r) = series.last().unwrap();
+            assert_eq!((s, i, r), (3, 0, 1));
+            total_time += time;
+        }
+        assert!((total_time / 2000.0 - 0.5).abs() < 0.05);
+
+        // Accounts that follow themselves (kept by read_directed_graph) still pass the infection down the chain
+        let self_follows = HashMap::from([(1, HashSet::new()), (2, HashSet::from([1, 2])), (3, HashSet::from([2, 3])), (4, HashSet::from([3, 4]))]);
+        let graph = DiffusionGraph::new(&self_follows);
+        let initial = [graph.index_of(1).unwrap()];
+        for _ in 0..100 {
+            let series = simulate_gillespie(&graph, no_recovery, &initial, 50.0, 1.0, &mut rng);
+            let &(_, s, i, r) = series.last().unwrap();
+            assert_eq!((s, i, r), (0, 4, 0));
+        }
+    }
+
+    // Components, betweenness and robustness to node removal
+    #[test]
+    fn test_robustness() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let graph = create_test_graph();
+        let betweenness = betweenness_centrality(&graph, 100, &mut rng);
+        // Node 3 sits on the paths 1-4 and 2-4; everyone else on none
+        assert_eq!(betweenness[&3], 2.0);
+        assert_eq!(betweenness[&1], 0.0);
+
+        let mut split = create_two_cliques();
+        split.get_mut(&4).unwrap().remove(&5);
+        split.get_mut(&5).unwrap().remove(&4);
+        split.insert(9, HashSet::new());
+        let components = connected_components(&split);
+        assert_eq!(components.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 4, 1]);
+        assert_eq!(components[0], vec![1, 2, 3, 4]);
+
+        // Removing the star's hub first leaves isolated leaves; every node is its own step, so R is exact
+        let star: HashMap<usize, HashSet<usize>> = (1..=4).map(|leaf| (leaf, HashSet::from([0]))).chain([(0, (1..=4).collect())]).collect();
+        let curve = percolation_curve(&star, RemovalStrategy::Degree, false, 5, &mut rng);
+        // The other nodes are 1 hop from the hub and 1.75 hops on average from each leaf
+        assert_eq!((curve[0].0, curve[0].1), (0.0, 1.0));
+        assert!((curve[0].2 - 1.6).abs() < 1e-9);
+        assert_eq!(curve[1].1, 0.2);
+        assert!((robustness_index(&curve) - (0.2 + 0.2 + 0.2 + 0.2 + 0.0) / 5.0).abs() < 1e-9);
+
+        // Random failures hit the hub no earlier than the attack does
+        let random = percolation_curve(&star, RemovalStrategy::Random, false, 5, &mut rng);
+        assert!(robustness_index(&random) >= robustness_index(&curve));
+        let adaptive = percolation_curve(&create_two_cliques(), RemovalStrategy::Betweenness(100), true, 8, &mut rng);
+        assert_eq!(adaptive.len(), 9);
+        assert_eq!(adaptive[1].1, 0.5);
+    }
+
+    // Articulation points, bridges and biconnected components
+    #[test]
+    fn test_biconnectivity() {
+        let graph = create_two_cliques();
+        assert_eq!(articulation_points(&graph), vec![4, 5]);
+        assert_eq!(bridges(&graph), vec![(4, 5)]);
+        assert_eq!(biconnected_components(&graph), vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![4, 5]]);
+
+        // A cycle has no weak points; the test graph's tail hangs off node 3
+        let cycle: HashMap<usize, HashSet<usize>> = (0..5).map(|i| (i, HashSet::from([(i + 1) % 5, (i + 4) % 5]))).collect();
+        assert!(articulation_points(&cycle).is_empty() && bridges(&cycle).is_empty());
+        assert_eq!(biconnected_components(&cycle).len(), 1);
+        assert_eq!(articulation_points(&create_test_graph()), vec![3]);
+
+        // A path long enough to overflow a recursive search
+        let n: usize = 100_000;
+        let path: HashMap<usize, HashSet<usize>> = (0..n)
+            .map(|i| (i, [i.checked_sub(1), (i + 1 < n).then_some(i + 1)].into_iter().flatten().collect()))
+            .collect();
+        assert_eq!(articulation_points(&path).len(), n - 2);
+        assert_eq!(bridges(&path).len(), n - 1);
+    }
+
+    // Eccentricities, radius, diameter, center and periphery of each component
+    #[test]
+    fn test_eccentricity() {
+        // A path 0-1-2-3-4 next to a star centered on 10
+        let mut graph: HashMap<usize, HashSet<usize>> = (0..5usize)
+            .map(|i| (i, [i.checked_sub(1), (i < 4).then_some(i + 1)].into_iter().flatten().collect()))
+            .collect();
+        graph.insert(10, (11..14).collect());
+        for leaf in 11..14 {
+            graph.insert(leaf, HashSet::from([10]));
+        }
+
+        let eccentricity = eccentricities(&graph);
+        assert_eq!((0..5).map(|i| eccentricity[&i]).collect::<Vec<_>>(), vec![4, 3, 2, 3, 4]);
+        assert_eq!(eccentricity[&10], 1);
+        assert_eq!(eccentricity[&11], 2);
+
+        let summaries = eccentricity_summary(&graph);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!((summaries[0].size, summaries[0].radius, summaries[0].diameter), (5, 2, 4));
+        assert_eq!((summaries[0].center.clone(), summaries[0].periphery.clone()), (vec![2], vec![0, 4]));
+        assert_eq!((summaries[1].radius, summaries[1].diameter, summaries[1].center.clone()), (1, 2, vec![10]));
+        assert_eq!(summaries[1].periphery, vec![11, 12, 13]);
+
+        // Matches plain BFS from every node on a denser graph
+        let graph = create_two_cliques();
+        let eccentricity = eccentricities(&graph);
+        for &node in graph.keys() {
+            let farthest = graph.keys().filter_map(|&other| shortest_path(&graph, node, other)).map(|path| path.len() - 1).max().unwrap();
+            assert_eq!(eccentricity[&node], farthest);
+        }
+    }
+
+    // Extreme eigenpairs, spectral gap, Fiedler vector and spectral clustering on graphs with known spectra
+    #[test]
+    fn test_spectral() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let pi = std::f64::consts::PI;
+
+        // A 200-node cycle: adjacency eigenvalues 2 cos(2 pi j / n), normalized Laplacian eigenvalues 1 - cos(2 pi j / n), mostly in close pairs
+        let n = 200;
+        let cycle: HashMap<usize, HashSet<usize>> = (0..n).map(|i| (i, HashSet::from([(i + 1) % n, (i + n - 1) % n]))).collect();
+        let (top, converged) = eigenpairs(&cycle, SpectralMatrix::Adjacency, 3, true, &mut rng);
+        assert!(converged);
+        let second = 2.0 * (2.0 * pi / n as f64).cos();
+        let expected = [2.0, second, second];
+        for ((value, vector), expected) in top.iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-6);
+            // Each vector is a unit eigenvector: M v = lambda v
+            assert!((vector.values().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-9);
+            for i in 0..n {
+                let product = vector[&((i + 1) % n)] + vector[&((i + n - 1) % n)];
+                assert!((product - value * vector[&i]).abs() < 1e-5);
+            }
+        }
+        let (bottom, converged) = eigenpairs(&cycle, SpectralMatrix::NormalizedLaplacian, 5, false, &mut rng);
+        assert!(converged);
+        let expected = [0, 1, 1, 2, 2].map(|j| 1.0 - (2.0 * pi * j as f64 / n as f64).cos());
+        for ((value, _), expected) in bottom.iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-6);
+        }
+        assert!((spectral_gap(&cycle, &mut rng) - (2.0 - second)).abs() < 1e-6);
+
+        // Complete graph on 6 nodes and a star with 9 leaves
+        let complete: HashMap<usize, HashSet<usize>> = (0..6).map(|i| (i, (0..6).filter(|&j| j != i).collect())).collect();
+        assert!((spectral_gap(&complete, &mut rng) - 6.0).abs() < 1e-9);
+        let (laplacian, _) = eigenpairs(&complete, SpectralMatrix::NormalizedLaplacian, 2, false, &mut rng);
+        assert!(laplacian[0].0.abs() < 1e-9 && (laplacian[1].0 - 1.2).abs() < 1e-9);
+        let mut star: HashMap<usize, HashSet<usize>> = HashMap::from([(0, (1..10).collect())]);
+        for leaf in 1..10 {
+            star.insert(leaf, HashSet::from([0]));
+        }
+        let (extremes, _) = eigenpairs(&star, SpectralMatrix::Adjacency, 1, true, &mut rng);
+        assert!((extremes[0].0 - 3.0).abs() < 1e-9);
+        assert!((eigenpairs(&star, SpectralMatrix::Adjacency, 1, false, &mut rng).0[0].0 + 3.0).abs() < 1e-9);
+
+        // A path's Fiedler value is 1 - cos(pi / (n - 1)), and its Fiedler vector cuts the path in half (the tail is not the largest component)
+        let mut path: HashMap<usize, HashSet<usize>> = (0..10usize)
+            .map(|i| (i, [i.checked_sub(1), (i < 9).then_some(i + 1)].into_iter().flatten().collect()))
+            .collect();
+        path.insert(100, HashSet::from([101]));
+        path.insert(101, HashSet::from([100]));
+        let (connectivity, fiedler) = fiedler_vector(&path, &mut rng).unwrap();
+        assert!((connectivity - (1.0 - (pi / 9.0).cos())).abs() < 1e-9);
+        assert_eq!(fiedler.len(), 10);
+        assert!((0..5).all(|i| fiedler[&i] > 0.0) && (5..10).all(|i| fiedler[&i] < 0.0));
+        assert!((1..10).all(|i| fiedler[&i] < fiedler[&(i - 1)]));
+
+        // Spectral clustering separates the two cliques
+        let clusters = spectral_clustering(&create_two_cliques(), 2, &mut rng);
+        assert!((1..=4).all(|node| clusters[&node] == clusters[&1]));
+        assert!((5..=8).all(|node| clusters[&node] == clusters[&5]));
+        assert_ne!(clusters[&1], clusters[&5]);
+    }
+}